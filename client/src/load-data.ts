@@ -24,6 +24,7 @@ export type ActiveFlightInfo = {
   from: AirportCode;
   to: AirportCode;
   waypoints: Waypoint[];
+  airways: (string | null)[];
 };
 
 export type ActiveFlight = {
@@ -30,6 +30,15 @@ sidebar.addPanel({
   title: "Flight information",
 });
 
+function routeString(flight: ActiveFlight): string {
+  return flight.info.waypoints
+    .map((w, i) => {
+      let airway = flight.info.airways[i];
+      return airway ? `${w.name} (${airway})` : w.name;
+    })
+    .join(", ");
+}
+
 export function updateFlightPanel(flight: ActiveFlight) {
   let departTime = new Date(flight.depart_time * 1000);
   let arrivalTime = new Date(flight.arrival_time * 1000);
@@ -46,7 +55,7 @@ export function updateFlightPanel(flight: ActiveFlight) {
     .replace("{departTime}", departTime.toLocaleTimeString())
     .replace("{arrivalTime}", arrivalTime.toLocaleTimeString())
     .replace("{duration}", `${delta_h}:${delta_min}:${delta_s}`)
-    .replace("{waypoints}", flight.info.waypoints.map((w) => w.name).join(", "))
+    .replace("{waypoints}", routeString(flight))
     .replace("{id}", flight.id.toString());
 }
 export function updateFlightPanel2(route: [number, number][]) {
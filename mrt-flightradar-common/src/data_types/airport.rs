@@ -29,15 +29,25 @@ pub enum AirFacility {
     Heliport {
         code: SmolStr,
         pad_coord: Pos<Vec2>,
+        /// Display name, eg. `Akane - Ishuzu International Airport (Hamanei)`
+        #[serde(default)]
+        name: Option<SmolStr>,
     },
     AirshipTerminal {
         code: SmolStr,
         pad_coord: Pos<Vec2>,
+        #[serde(default)]
+        name: Option<SmolStr>,
     },
     Airport {
         code: SmolStr,
         ty: PlaneFacilityType,
         runways: SmallVec<[Runway; 1]>,
+        #[serde(default)]
+        name: Option<SmolStr>,
+        /// Overrides the centroid of the runways as the airport reference point
+        #[serde(default)]
+        reference_point: Option<Pos<Vec2>>,
     },
 }
 impl AirFacility {
@@ -50,11 +60,99 @@ impl AirFacility {
         }
     }
     #[must_use]
-    pub fn main_coord(&self) -> Option<&Pos<Vec2>> {
+    pub const fn name(&self) -> Option<&SmolStr> {
         match &self {
-            Self::Heliport { pad_coord, .. } => Some(pad_coord),
-            Self::AirshipTerminal { pad_coord, .. } => Some(pad_coord),
-            Self::Airport { runways, .. } => runways.get(1).map(|r| &r.vec.tail),
+            Self::Heliport { name, .. }
+            | Self::AirshipTerminal { name, .. }
+            | Self::Airport { name, .. } => name.as_ref(),
         }
     }
+    pub const fn name_mut(&mut self) -> &mut Option<SmolStr> {
+        match self {
+            Self::Heliport { name, .. }
+            | Self::AirshipTerminal { name, .. }
+            | Self::Airport { name, .. } => name,
+        }
+    }
+    /// Reference point of the facility, which distances to it are measured from. This is the pad
+    /// of heliports and airship terminals, and the centroid of the ends of the runways of airports
+    /// unless the source sheet gives one
+    #[must_use]
+    pub fn main_coord(&self) -> Option<Pos<Vec2>> {
+        match &self {
+            Self::Heliport { pad_coord, .. } | Self::AirshipTerminal { pad_coord, .. } => {
+                Some(*pad_coord)
+            }
+            Self::Airport {
+                runways,
+                reference_point,
+                ..
+            } => reference_point.or_else(|| {
+                let ends = runways
+                    .iter()
+                    .flat_map(|r| [r.vec.tail, r.vec.head()])
+                    .collect::<Vec<_>>();
+                (!ends.is_empty()).then(|| ends.iter().sum::<Vec2>() / ends.len() as f32)
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::{eyre, Result};
+    use glam::{vec2, Vec2};
+
+    use crate::data_types::{airport::AirFacility, RawData};
+
+    #[test]
+    fn airport_reference_points() -> Result<()> {
+        let raw_data = RawData::embedded()?;
+        let main_coord = |code: &str| {
+            raw_data
+                .air_facilities
+                .iter()
+                .find(|af| af.code() == code)
+                .and_then(AirFacility::main_coord)
+                .ok_or_else(|| eyre!("No reference point for {code}"))
+        };
+        // one runway, whose midpoint is the reference point
+        assert_eq!(main_coord("KBN")?, vec2(22623.0, 28867.0));
+        // two parallel runways
+        assert_eq!(main_coord("FYI")?, vec2(3523.5, -10490.5));
+
+        for af in &raw_data.air_facilities {
+            let AirFacility::Airport { runways, .. } = af else {
+                continue;
+            };
+            let Some(coord) = af.main_coord() else {
+                assert!(runways.is_empty(), "{} has no reference point", af.code());
+                continue;
+            };
+            let ends = runways.iter().flat_map(|r| [r.vec.tail, r.vec.head()]);
+            let (min, max) = ends.fold((Vec2::MAX, Vec2::MIN), |(min, max), end| {
+                (min.min(end), max.max(end))
+            });
+            assert!(
+                min.cmple(coord + 1e-3).all() && max.cmpge(coord - 1e-3).all(),
+                "Reference point of {} is outside its runways",
+                af.code()
+            );
+        }
+
+        let mut airport = raw_data
+            .air_facilities
+            .iter()
+            .find(|af| af.code() == "KBN")
+            .cloned()
+            .ok_or_else(|| eyre!("No KBN"))?;
+        if let AirFacility::Airport {
+            reference_point, ..
+        } = &mut airport
+        {
+            *reference_point = Some(vec2(1.0, 2.0));
+        }
+        assert_eq!(airport.main_coord(), Some(vec2(1.0, 2.0)));
+        Ok(())
+    }
 }
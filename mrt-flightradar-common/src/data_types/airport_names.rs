@@ -0,0 +1,310 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Bound,
+};
+
+use cached::once_cell::sync::Lazy;
+use color_eyre::eyre::{eyre, Result};
+use itertools::Itertools;
+use smol_str::SmolStr;
+
+use crate::data_types::{airport::AirFacility, timetable::AirportCode};
+
+/// Names of the airports in `data/airport_names.txt`, parsed once
+pub static AIRPORT_NAMES: Lazy<AirportNames> = Lazy::new(|| {
+    AirportNames::from_string(include_str!("../../../data/airport_names.txt"))
+        .unwrap_or_else(|err| panic!("Invalid airport names: {err}"))
+});
+
+/// Lowercases `s` and turns everything but letters and digits into single spaces, so that
+/// `Akane - Ishuzu` and `akane ishuzu` compare equal
+fn simplified(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .join(" ")
+}
+
+/// How well `query` matches `name`, both [`simplified`], lower being better. Beyond a prefix or
+/// substring, the letters of `query` may be spread out over `name` as long as the match isn't
+/// more than twice as long as `query`, which catches most typos that leave out letters
+fn match_score(name: &str, query: &str) -> Option<usize> {
+    if name.starts_with(query) {
+        return Some(0);
+    }
+    if name.split(' ').any(|word| word.starts_with(query)) {
+        return Some(1);
+    }
+    if name.contains(query) {
+        return Some(2);
+    }
+    let query = query.chars().filter(|c| *c != ' ').collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    (0..name.len())
+        .filter(|start| query.first() == Some(&name[*start]))
+        .filter_map(|start| {
+            let mut query = query.iter().peekable();
+            for (i, c) in name.iter().enumerate().skip(start) {
+                if query.next_if_eq(&c).is_some() && query.peek().is_none() {
+                    return Some(i + 1 - start);
+                }
+            }
+            None
+        })
+        .min()
+        .filter(|len| *len <= query.len() * 2)
+        .map(|len| 3 + len - query.len())
+}
+
+/// Airport names by code and codes by name. Lines of the file are a name and a code separated by
+/// a tab, or only a name for airports without a code. Codes listed more than once keep their
+/// first name, and the later ones are only used to look up the code
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AirportNames {
+    names: BTreeMap<AirportCode, SmolStr>,
+    codes: Vec<(SmolStr, Option<AirportCode>)>,
+}
+
+impl AirportNames {
+    pub fn from_string(s: &str) -> Result<Self> {
+        let mut airport_names = Self::default();
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (name, code) = match line.split_once('\t') {
+                Some((name, code)) => (name.trim(), Some(code.trim())),
+                None => (line.trim(), None),
+            };
+            if name.is_empty() {
+                return Err(eyre!("Line {}: No airport name", i + 1));
+            }
+            if let Some(code) = code {
+                if code.is_empty() || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    return Err(eyre!("Line {}: Invalid airport code `{code}`", i + 1));
+                }
+                airport_names
+                    .names
+                    .entry(code.to_uppercase().into())
+                    .or_insert_with(|| name.into());
+            }
+            airport_names
+                .codes
+                .push((name.into(), code.map(|code| code.to_uppercase().into())));
+        }
+        Ok(airport_names)
+    }
+
+    /// These names with the names of `air_facilities` taking precedence, eg. those merged into
+    /// the raw data by the data updater. Names replaced this way can still be searched for
+    #[must_use]
+    pub fn merged(&self, air_facilities: &[AirFacility]) -> Self {
+        let mut merged = Self::default();
+        for af in air_facilities {
+            if let Some(name) = af.name() {
+                merged.names.insert(af.code().to_owned(), name.to_owned());
+                merged
+                    .codes
+                    .push((name.to_owned(), Some(af.code().to_owned())));
+            }
+        }
+        for (code, name) in &self.names {
+            merged
+                .names
+                .entry(code.to_owned())
+                .or_insert_with(|| name.to_owned());
+        }
+        merged.codes.extend(self.codes.iter().cloned());
+        merged
+    }
+
+    #[must_use]
+    pub fn name(&self, code: &str) -> Option<&SmolStr> {
+        self.names.get(code)
+    }
+
+    /// Code of the airport with exactly this name, ignoring case and punctuation
+    #[must_use]
+    pub fn code(&self, name: &str) -> Option<&AirportCode> {
+        let name = simplified(name);
+        self.codes
+            .iter()
+            .find(|(n, _)| simplified(n) == name)
+            .and_then(|(_, code)| code.as_ref())
+    }
+
+    pub fn codes(&self) -> impl Iterator<Item = &AirportCode> {
+        self.names.keys()
+    }
+
+    /// Names listed without a code
+    pub fn without_code(&self) -> impl Iterator<Item = &SmolStr> {
+        self.codes
+            .iter()
+            .filter(|(_, code)| code.is_none())
+            .map(|(name, _)| name)
+    }
+
+    /// Airports with a code starting with `prefix`, in order of their code
+    pub fn with_prefix<'a>(
+        &'a self,
+        prefix: &str,
+    ) -> impl Iterator<Item = (&'a AirportCode, &'a SmolStr)> + 'a {
+        let prefix = prefix.to_uppercase();
+        self.names
+            .range::<str, _>((Bound::Included(prefix.as_str()), Bound::Unbounded))
+            .take_while(move |(code, _)| code.starts_with(&prefix))
+    }
+
+    /// Airports whose code is `query` or whose name matches it, best match first. `akane` finds
+    /// `HMN` (Akane - Ishuzu International Airport), as does `akne`
+    #[must_use]
+    pub fn search(&self, query: &str) -> Vec<(&AirportCode, &SmolStr)> {
+        let query = simplified(query);
+        if query.is_empty() {
+            return vec![];
+        }
+        let mut seen = BTreeSet::new();
+        self.names
+            .get_key_value(query.to_uppercase().as_str())
+            .map(|code| (0, code))
+            .into_iter()
+            .chain(self.codes.iter().filter_map(|(alias, code)| {
+                let airport = self.names.get_key_value(code.as_ref()?)?;
+                let score = match_score(&simplified(alias), &query)?;
+                Some((score + 1, airport))
+            }))
+            .sorted_by_key(|(score, (code, _))| (*score, *code))
+            .filter(|(_, (code, _))| seen.insert(*code))
+            .map(|(_, airport)| airport)
+            .collect()
+    }
+
+    /// Codes of air facilities in the raw data that aren't named here
+    #[must_use]
+    pub fn unnamed<'a>(&self, air_facilities: &'a [AirFacility]) -> Vec<&'a AirportCode> {
+        air_facilities
+            .iter()
+            .map(AirFacility::code)
+            .filter(|code| !self.names.contains_key(*code))
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    /// Codes named here that aren't in the raw data
+    #[must_use]
+    pub fn not_in_raw_data(&self, air_facilities: &[AirFacility]) -> Vec<&AirportCode> {
+        let codes = air_facilities
+            .iter()
+            .map(AirFacility::code)
+            .collect::<BTreeSet<_>>();
+        self.codes().filter(|code| !codes.contains(code)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use itertools::Itertools;
+    use smol_str::SmolStr;
+
+    use crate::data_types::{
+        airport::AirFacility,
+        airport_names::{AirportNames, AIRPORT_NAMES},
+        timetable::AirportCode,
+        RawData,
+    };
+
+    #[test]
+    fn airport_names_file_is_valid() {
+        assert!(AIRPORT_NAMES.codes().next().is_some(), "No airport names");
+    }
+
+    #[test]
+    fn airport_names_lookup() -> Result<()> {
+        let airport_names = AirportNames::from_string(
+            "Akane - Ishuzu International Airport (Hamanei)\tHMN\n\
+             Airchester Airfield\tACA\n\
+             Airchester\taca\n\
+             Aprix Airfield (unnamed)\n\
+             Hamburg Airport\tHAM\n",
+        )?;
+        assert_eq!(
+            airport_names.name("ACA").map(SmolStr::as_str),
+            Some("Airchester Airfield")
+        );
+        assert_eq!(
+            airport_names.code("akane ishuzu international airport hamanei"),
+            Some(&"HMN".into())
+        );
+        assert_eq!(airport_names.code("Airchester"), Some(&"ACA".into()));
+        assert_eq!(airport_names.code("Aprix Airfield (unnamed)"), None);
+
+        let search = |query| {
+            airport_names
+                .search(query)
+                .into_iter()
+                .map(|(code, _)| code.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(search("akane"), ["HMN"]);
+        assert_eq!(search("akne"), ["HMN"]);
+        assert_eq!(search("ham"), ["HAM", "HMN"]);
+        assert_eq!(search("air"), ["ACA", "HAM", "HMN"]);
+        assert_eq!(search("airchester"), ["ACA"]);
+        assert!(search("xyz").is_empty());
+        assert!(search(" - ").is_empty());
+
+        assert_eq!(
+            airport_names.with_prefix("h").map(|(c, _)| c).join(","),
+            "HAM,HMN"
+        );
+        assert!(AirportNames::from_string("Airport\t").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn airport_names_merged() -> Result<()> {
+        let mut raw_data = RawData::embedded()?;
+        let code = raw_data.air_facilities[0].code().to_owned();
+        *raw_data.air_facilities[0].name_mut() = Some("Raw Data Name".into());
+        let airport_names = AirportNames::from_string(&format!("Old Name\t{code}\nTest\tZZZ"))?
+            .merged(&raw_data.air_facilities);
+        assert_eq!(
+            airport_names.name(&code).map(SmolStr::as_str),
+            Some("Raw Data Name")
+        );
+        assert_eq!(airport_names.name("ZZZ").map(SmolStr::as_str), Some("Test"));
+        assert_eq!(airport_names.search("raw data name")[0].0, &code);
+        assert_eq!(airport_names.search("old name")[0].0, &code);
+        Ok(())
+    }
+
+    #[test]
+    fn airport_names_raw_data() -> Result<()> {
+        let raw_data = RawData::embedded()?;
+        let airport_names = AirportNames::from_string("Test\tZZZ")?;
+        assert_eq!(
+            airport_names.not_in_raw_data(&raw_data.air_facilities),
+            [&AirportCode::from("ZZZ")]
+        );
+        assert_eq!(
+            airport_names.unnamed(&raw_data.air_facilities).len(),
+            raw_data
+                .air_facilities
+                .iter()
+                .map(AirFacility::code)
+                .unique()
+                .count()
+        );
+        Ok(())
+    }
+}
@@ -5,6 +5,9 @@ use smol_str::SmolStr;
 pub struct Airway {
     pub waypoint1: SmolStr,
     pub waypoint2: SmolStr,
+    /// Ident of the named airway this is a part of, eg. `A1`
+    #[serde(default)]
+    pub name: Option<SmolStr>,
 }
 impl PartialEq for Airway {
     fn eq(&self, other: &Self) -> bool {
@@ -12,3 +15,10 @@ impl PartialEq for Airway {
             || (self.waypoint2 == other.waypoint2 && self.waypoint1 == other.waypoint1)
     }
 }
+impl Airway {
+    #[must_use]
+    pub fn connects(&self, waypoint1: &SmolStr, waypoint2: &SmolStr) -> bool {
+        (self.waypoint1 == *waypoint1 && self.waypoint2 == *waypoint2)
+            || (self.waypoint1 == *waypoint2 && self.waypoint2 == *waypoint1)
+    }
+}
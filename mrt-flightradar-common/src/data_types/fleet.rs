@@ -0,0 +1,177 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{eyre, Result};
+use smol_str::SmolStr;
+
+use crate::data_types::timetable::Flight;
+
+const AIRCRAFT_TYPES: &str = include_str!("../../../data/aircraft_types.txt");
+
+/// Aircraft types in the shared catalogue, `data/aircraft_types.txt`
+pub fn aircraft_types() -> impl Iterator<Item = &'static str> {
+    AIRCRAFT_TYPES
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Looks up an aircraft type in the catalogue. If it isn't there, the error suggests a type that
+/// only differs in case, spaces or punctuation, eg. `Stratus SA-1` for `stratus sa1`
+pub fn find_aircraft_type(name: &str) -> Result<&'static str> {
+    let simplified = |name: &str| {
+        name.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_lowercase()
+    };
+    if let Some(ty) = aircraft_types().find(|ty| *ty == name) {
+        return Ok(ty);
+    }
+    match aircraft_types().find(|ty| simplified(ty) == simplified(name)) {
+        Some(ty) => Err(eyre!(
+            "Unknown aircraft type `{name}`, did you mean `{ty}`?"
+        )),
+        None => Err(eyre!("Unknown aircraft type `{name}`")),
+    }
+}
+
+/// An airline's aircraft, by registry. Saved next to the airline's timetable as `<airline>.fleet`,
+/// with a registry and its aircraft type separated by a tab on each line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fleet(pub BTreeMap<SmolStr, SmolStr>);
+
+impl Fleet {
+    /// Path of the fleet file of the timetable at `timetable`
+    #[must_use]
+    pub fn path(timetable: &Path) -> PathBuf {
+        timetable.with_extension("fleet")
+    }
+
+    /// Parses a fleet file. Blank lines and lines starting with `#` are skipped
+    pub fn from_string(s: &str) -> Result<Self> {
+        let mut fleet = Self::default();
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((registry, aircraft)) = line.split_once('\t') else {
+                return Err(eyre!(
+                    "Line {}: Expected a registry and aircraft type separated by a tab",
+                    i + 1
+                ));
+            };
+            let (registry, aircraft) = (registry.trim(), aircraft.trim());
+            if fleet.0.insert(registry.into(), aircraft.into()).is_some() {
+                return Err(eyre!("Line {}: Registry {registry} is listed twice", i + 1));
+            }
+        }
+        Ok(fleet)
+    }
+
+    /// Reads the fleet of the timetable at `timetable`, which is empty if it has no fleet file
+    pub fn from_timetable_path(timetable: &Path) -> Result<Self> {
+        let path = Self::path(timetable);
+        if path.exists() {
+            Self::from_string(&fs::read_to_string(path)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    #[must_use]
+    pub fn aircraft(&self, registry: &str) -> Option<&SmolStr> {
+        self.0.get(registry)
+    }
+
+    /// Warnings about aircraft types that aren't in the catalogue, and flights flown by a
+    /// registry of the fleet with a different aircraft
+    #[must_use]
+    pub fn check(&self, flights: &[Flight]) -> Vec<String> {
+        let fleet = self.0.iter().filter_map(|(registry, aircraft)| {
+            find_aircraft_type(aircraft)
+                .err()
+                .map(|err| format!("Fleet: {registry}: {err}"))
+        });
+        let flights = flights.iter().enumerate().filter_map(|(i, flight)| {
+            match self.aircraft(&flight.registry) {
+                Some(aircraft) if *aircraft != flight.aircraft => Some(format!(
+                    "Flight {i}: {} is a `{aircraft}` in the fleet, not a `{}`",
+                    flight.registry, flight.aircraft
+                )),
+                Some(_) => None,
+                None => find_aircraft_type(&flight.aircraft)
+                    .err()
+                    .map(|err| format!("Flight {i}: {err}")),
+            }
+        });
+        fleet.chain(flights).collect()
+    }
+}
+
+impl Display for Fleet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (registry, aircraft) in &self.0 {
+            writeln!(f, "{registry}\t{aircraft}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use smol_str::SmolStr;
+
+    use crate::data_types::{
+        fleet::{aircraft_types, find_aircraft_type, Fleet},
+        timetable::AirlineTimetable,
+    };
+
+    #[test]
+    fn aircraft_types_file_is_valid() {
+        assert!(aircraft_types().count() > 0);
+        assert!(aircraft_types().all(|ty| find_aircraft_type(ty).is_ok_and(|found| found == ty)));
+    }
+
+    #[test]
+    fn find_aircraft_type_suggestion() {
+        let err = find_aircraft_type("Stratus SA1").map_err(|err| err.to_string());
+        assert_eq!(
+            err,
+            Err("Unknown aircraft type `Stratus SA1`, did you mean `Stratus SA-1`?".into())
+        );
+        assert!(find_aircraft_type("Test").is_err());
+    }
+
+    #[test]
+    fn fleet_check() -> Result<()> {
+        let raw = "# Fleet\nREG\tStratus SA-1\nOLD\tStratus SA1\n";
+        let fleet = Fleet::from_string(raw)?;
+        assert_eq!(
+            fleet.aircraft("REG").map(SmolStr::as_str),
+            Some("Stratus SA-1")
+        );
+        assert_eq!(fleet.to_string(), "OLD\tStratus SA1\nREG\tStratus SA-1\n");
+        assert!(Fleet::from_string("REG Stratus SA-1").is_err());
+        assert!(Fleet::from_string("REG\tDragon\nREG\tDragon").is_err());
+
+        let timetable = AirlineTimetable::from_string(
+            r#""Test",REG;AB123,ABC,0000
+"Dragon",OTHER;CD456,DEF,0000"#,
+            "Test".into(),
+        )?;
+        assert_eq!(
+            fleet.check(&timetable.flights),
+            [
+                "Fleet: OLD: Unknown aircraft type `Stratus SA1`, did you mean `Stratus SA-1`?",
+                "Flight 0: REG is a `Stratus SA-1` in the fleet, not a `Test`"
+            ]
+        );
+        Ok(())
+    }
+}
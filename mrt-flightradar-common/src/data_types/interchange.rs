@@ -0,0 +1,297 @@
+//! Timetables in formats for other programs: CSV for spreadsheets, JSON, and iCalendar to
+//! share schedules with players
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use color_eyre::eyre::{eyre, Result};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+use crate::data_types::{
+    time::Time,
+    timetable::{AirlineTimetable, AirportCode, Days, Flight, FlightSegment},
+};
+
+const ICAL_DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+const WEEKDAYS: [(Weekday, &str); 7] = [
+    (Weekday::Mon, "MO"),
+    (Weekday::Tue, "TU"),
+    (Weekday::Wed, "WE"),
+    (Weekday::Thu, "TH"),
+    (Weekday::Fri, "FR"),
+    (Weekday::Sat, "SA"),
+    (Weekday::Sun, "SU"),
+];
+
+/// A segment of a flight. Rows of the same flight share its `flight` number and are in order
+#[derive(Debug, Serialize, Deserialize)]
+struct CsvRow {
+    flight: usize,
+    aircraft: SmolStr,
+    registry: SmolStr,
+    /// Every day if empty
+    days: Option<Days>,
+    valid_from: Option<NaiveDate>,
+    valid_to: Option<NaiveDate>,
+    flight_no: SmolStr,
+    airport: AirportCode,
+    arrive_time: Option<SmolStr>,
+    depart_time: SmolStr,
+}
+
+/// Parses a time from a spreadsheet, which may have dropped leading zeros, eg. `930` for `0930`
+fn parse_csv_time(s: &str) -> Result<Time> {
+    let s = s.trim();
+    let hhmm = s.split('+').next().unwrap_or_default();
+    format!("{hhmm:0>4}{}", s.get(hhmm.len()..).unwrap_or_default())
+        .parse()
+        .map_err(|err| eyre!("Invalid time `{s}`: {err}"))
+}
+
+/// Escapes `,`, `;`, `\` and newlines in iCalendar text
+fn ical_text(s: &str) -> String {
+    s.replace('\\', r"\\")
+        .replace(';', r"\;")
+        .replace(',', r"\,")
+        .replace('\n', r"\n")
+}
+
+impl AirlineTimetable {
+    /// Parses a CSV file with a header row and one row per flight segment, as written by
+    /// [`Self::to_csv`]. Comments aren't kept in CSV files
+    pub fn from_csv(s: &str, name: SmolStr) -> Result<Self> {
+        let mut flights: Vec<(usize, Flight)> = vec![];
+        for (i, row) in csv::Reader::from_reader(s.as_bytes())
+            .deserialize::<CsvRow>()
+            .enumerate()
+        {
+            let parse_row = || -> Result<_> {
+                let row = row?;
+                let segment = FlightSegment {
+                    flight_no: row.flight_no.to_owned(),
+                    airport: row.airport.to_uppercase().into(),
+                    depart_time: parse_csv_time(&row.depart_time)?,
+                    arrive_time: row
+                        .arrive_time
+                        .as_deref()
+                        .filter(|t| !t.trim().is_empty())
+                        .map(parse_csv_time)
+                        .transpose()?,
+                };
+                Ok((row, segment))
+            };
+            // the header is row 1
+            let (row, segment) = parse_row().map_err(|err| eyre!("Row {}: {err}", i + 2))?;
+            match flights.last_mut() {
+                Some((n, flight)) if *n == row.flight => flight.segments.push(segment),
+                _ => flights.push((
+                    row.flight,
+                    Flight {
+                        aircraft: row.aircraft,
+                        registry: row.registry,
+                        segments: vec![segment],
+                        comments: vec![],
+                        days: row.days.unwrap_or_default(),
+                        valid_from: row.valid_from,
+                        valid_to: row.valid_to,
+                    },
+                )),
+            }
+        }
+        let flights = flights.into_iter().map(|(_, f)| f).collect::<Vec<_>>();
+        Self::validate_flights(&flights)?;
+        Ok(Self {
+            name,
+            comments: vec![],
+            flights,
+        })
+    }
+
+    pub fn to_csv(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        for (i, flight) in self.flights.iter().enumerate() {
+            for seg in &flight.segments {
+                writer.serialize(CsvRow {
+                    flight: i,
+                    aircraft: flight.aircraft.to_owned(),
+                    registry: flight.registry.to_owned(),
+                    days: Some(flight.days),
+                    valid_from: flight.valid_from,
+                    valid_to: flight.valid_to,
+                    flight_no: seg.flight_no.to_owned(),
+                    airport: seg.airport.to_owned(),
+                    arrive_time: seg.arrive_time.map(|t| t.to_string().into()),
+                    depart_time: seg.depart_time.to_string().into(),
+                })?;
+            }
+        }
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+
+    pub fn from_json(s: &str) -> Result<Self> {
+        let timetable = serde_json::from_str::<Self>(s)?;
+        Self::validate_flights(&timetable.flights)?;
+        Ok(timetable)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Writes every leg of every flight as a repeating iCalendar event, starting from the first
+    /// day the flight operates on from `now`. Flights that don't operate after `now` are left out.
+    /// Event times are floating, ie. in the local time of whoever opens the calendar
+    #[must_use]
+    pub fn to_ical(&self, now: DateTime<Utc>) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".into(),
+            "VERSION:2.0".into(),
+            "PRODID:-//mrt-flightradar//timetable-editor//EN".into(),
+            format!("X-WR-CALNAME:{}", ical_text(&self.name)),
+        ];
+        for (i, flight) in self.flights.iter().enumerate() {
+            let from = flight
+                .valid_from
+                .map_or_else(|| now.date_naive(), |from| from.max(now.date_naive()));
+            let Some(first) = (0..7)
+                .map(|n| from + Duration::days(n))
+                .find(|date| flight.operates_on(*date))
+            else {
+                continue;
+            };
+            for (leg, (seg, next)) in flight.segments.iter().tuple_windows().enumerate() {
+                let Some(block) = flight.block_time(leg) else {
+                    continue;
+                };
+                let start = first.and_time(NaiveTime::MIN) + Duration::from(seg.depart_time);
+                let end = start + Duration::minutes(block.into());
+                let day = i64::from(seg.depart_time.day());
+                let days = flight.days.rotated(day);
+                let mut rule = if days == Days::ALL {
+                    "FREQ=DAILY".to_owned()
+                } else {
+                    format!(
+                        "FREQ=WEEKLY;BYDAY={}",
+                        WEEKDAYS
+                            .iter()
+                            .filter(|(weekday, _)| days.contains(*weekday))
+                            .map(|(_, code)| code)
+                            .join(",")
+                    )
+                };
+                if let Some(to) = flight.valid_to {
+                    let until = (to + Duration::days(day)).and_time(NaiveTime::MIN)
+                        + Duration::days(1)
+                        - Duration::seconds(1);
+                    rule.push_str(&format!(";UNTIL={}", until.format(ICAL_DATE_TIME_FORMAT)));
+                }
+                lines.extend([
+                    "BEGIN:VEVENT".into(),
+                    format!("UID:{}-{i}-{leg}@mrt-flightradar", ical_text(&self.name)),
+                    format!("DTSTAMP:{}Z", now.format(ICAL_DATE_TIME_FORMAT)),
+                    format!("DTSTART:{}", start.format(ICAL_DATE_TIME_FORMAT)),
+                    format!("DTEND:{}", end.format(ICAL_DATE_TIME_FORMAT)),
+                    format!("RRULE:{rule}"),
+                    format!(
+                        "SUMMARY:{} {}-{}",
+                        ical_text(&seg.flight_no),
+                        ical_text(&seg.airport),
+                        ical_text(&next.airport)
+                    ),
+                    format!(
+                        "DESCRIPTION:{} {}",
+                        ical_text(&flight.aircraft),
+                        ical_text(&flight.registry)
+                    ),
+                    "END:VEVENT".into(),
+                ]);
+            }
+        }
+        lines.push("END:VCALENDAR".into());
+        lines.iter().map(|line| format!("{line}\r\n")).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use color_eyre::eyre::{eyre, Result};
+    use itertools::Itertools;
+
+    use crate::data_types::timetable::AirlineTimetable;
+
+    fn timetable() -> Result<AirlineTimetable> {
+        AirlineTimetable::from_string(
+            r#"
+#!fpln 5
+#!name Test Airline
+"Test, Aircraft",REG;AB123,ABC,2330;CD456,DEF,0100+1,0045+1|days=1......|to=2026-06-30
+"Test",REG;EF789,ABC,0900;GH012,DEF,1000
+            "#
+            .trim(),
+            "Test".into(),
+        )
+    }
+
+    #[test]
+    fn csv_round_trip() -> Result<()> {
+        let timetable = timetable()?;
+        let csv = timetable.to_csv()?;
+        assert_eq!(
+            csv.lines().nth(1),
+            Some(r#"0,"Test, Aircraft",REG,1......,,2026-06-30,AB123,ABC,,2330"#)
+        );
+        assert_eq!(
+            AirlineTimetable::from_csv(&csv, "Test Airline".into())?,
+            timetable
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn csv_spreadsheet() -> Result<()> {
+        let csv = "flight,aircraft,registry,days,valid_from,valid_to,flight_no,airport,arrive_time,depart_time
+1,Test,REG,,,,AB123,abc,,930
+1,Test,REG,,,,CD456,def,45+1,100+1";
+        let timetable = AirlineTimetable::from_csv(csv, "Test".into())?;
+        assert_eq!(
+            timetable.flights[0].segments.iter().join(";"),
+            "AB123,ABC,0930;CD456,DEF,0100+1,0045+1"
+        );
+        assert!(
+            AirlineTimetable::from_csv(&csv.replace("100+1", "0900"), "Test".into()).is_err(),
+            "Invalid flight did not error"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn json_round_trip() -> Result<()> {
+        let timetable = timetable()?;
+        let json = timetable.to_json()?;
+        assert!(json.contains(r#""depart_time": "0100+1""#), "{json}");
+        assert_eq!(AirlineTimetable::from_json(&json)?, timetable);
+        Ok(())
+    }
+
+    #[test]
+    fn ical_export() -> Result<()> {
+        let now = NaiveDate::from_ymd_opt(2026, 6, 3)
+            .and_then(|d| d.and_hms_opt(12, 0, 0))
+            .ok_or_else(|| eyre!("Invalid date"))?;
+        let ical = timetable()?.to_ical(Utc.from_utc_datetime(&now));
+        assert_eq!(ical.matches("BEGIN:VEVENT").count(), 2);
+        for line in [
+            "DTSTAMP:20260603T120000Z",
+            "DTSTART:20260608T233000",
+            "DTEND:20260609T004500",
+            "RRULE:FREQ=WEEKLY;BYDAY=MO;UNTIL=20260630T235959",
+            r"DESCRIPTION:Test\, Aircraft REG",
+            "DTSTART:20260603T090000",
+            "RRULE:FREQ=DAILY",
+        ] {
+            assert!(ical.contains(&format!("{line}\r\n")), "Missing `{line}`");
+        }
+        Ok(())
+    }
+}
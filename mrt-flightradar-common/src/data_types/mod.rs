@@ -1,10 +1,26 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock},
+};
+
 use cached::once_cell::sync::Lazy;
+use color_eyre::eyre::{eyre, Result};
 use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use tracing::{info, warn};
 
-use crate::data_types::{airport::AirFacility, airway::Airway, waypoint::Waypoint};
+use crate::data_types::{
+    airport::AirFacility, airway::Airway, raw_data_header::RawDataHeader, waypoint::Waypoint,
+};
 
 pub mod airport;
+pub mod airport_names;
 pub mod airway;
+pub mod fleet;
+pub mod interchange;
+pub mod raw_data_header;
+pub mod rotation;
 pub mod time;
 pub mod timetable;
 pub mod vec;
@@ -16,6 +32,139 @@ pub struct RawData {
     pub waypoints: Vec<Waypoint>,
     pub airways: Vec<Airway>,
 }
-pub static RAW_DATA: Lazy<RawData> = Lazy::new(|| {
-    rmp_serde::from_slice::<RawData>(include_bytes!("../../../data/raw_data")).unwrap()
-});
+impl RawData {
+    pub fn from_slice_with_header(bytes: &[u8]) -> Result<(Option<RawDataHeader>, Self)> {
+        let (header, body) = RawDataHeader::decode(bytes)?;
+        let raw_data =
+            rmp_serde::from_slice(body).map_err(|err| eyre!("Invalid raw data: {err}"))?;
+        Ok((header, raw_data))
+    }
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        Ok(Self::from_slice_with_header(bytes)?.1)
+    }
+    /// Serialises with a header recording the current time and the given sources
+    pub fn to_vec(&self, sources: Vec<SmolStr>) -> Result<Vec<u8>> {
+        let body = rmp_serde::to_vec(self)?;
+        RawDataHeader::new(&body, sources).encode(&body)
+    }
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let bytes =
+            fs::read(path).map_err(|err| eyre!("Could not read `{}`: {err}", path.display()))?;
+        let (header, raw_data) = Self::from_slice_with_header(&bytes)?;
+        if let Some(header) = header {
+            info!(?path, ?header, "Read raw data header");
+        } else {
+            info!(?path, "Raw data has no header, reading as format version 0");
+        }
+        Ok(raw_data)
+    }
+    /// The copy of `data/raw_data` embedded at compile time
+    pub fn embedded() -> Result<Self> {
+        Self::from_slice(include_bytes!("../../../data/raw_data"))
+    }
+    /// Loads from `path` if given, falling back to the embedded copy if that fails
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        if let Some(path) = path {
+            match Self::from_file(path) {
+                Ok(raw_data) => {
+                    info!(?path, "Loaded raw data");
+                    return Ok(raw_data);
+                }
+                Err(err) => warn!("{err}, falling back to embedded raw data"),
+            }
+        }
+        Self::embedded()
+    }
+
+    #[must_use]
+    pub fn airway_between(&self, waypoint1: &SmolStr, waypoint2: &SmolStr) -> Option<&Airway> {
+        self.airways
+            .iter()
+            .find(|aw| aw.connects(waypoint1, waypoint2))
+    }
+    /// Idents of the named airways flown between each pair of consecutive waypoints
+    #[must_use]
+    pub fn airway_names(&self, waypoints: &[&Waypoint]) -> Vec<Option<&SmolStr>> {
+        waypoints
+            .windows(2)
+            .map(|w| {
+                self.airway_between(&w[0].name, &w[1].name)
+                    .and_then(|aw| aw.name.as_ref())
+            })
+            .collect()
+    }
+}
+/// Environment variable holding the path to load [`RAW_DATA`] from
+pub const RAW_DATA_PATH_VAR: &str = "MRT_FLIGHTRADAR_RAW_DATA";
+
+#[must_use]
+pub fn raw_data_path() -> Option<PathBuf> {
+    std::env::var_os(RAW_DATA_PATH_VAR).map(Into::into)
+}
+
+/// The dataset currently in use, which can be swapped out at runtime.
+///
+/// Each dataset is handed out as its own [`Arc`], so anything still using a replaced dataset keeps
+/// it alive until it's done with it
+pub struct CurrentRawData(Lazy<RwLock<Arc<RawData>>>);
+impl CurrentRawData {
+    #[must_use]
+    pub fn get(&self) -> Arc<RawData> {
+        Arc::clone(&self.0.read().unwrap_or_else(PoisonError::into_inner))
+    }
+    pub fn set(&self, raw_data: RawData) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(raw_data);
+    }
+}
+
+pub static RAW_DATA: CurrentRawData = CurrentRawData(Lazy::new(|| {
+    let raw_data = RawData::load(raw_data_path().as_deref())
+        .unwrap_or_else(|err| panic!("No usable raw data: {err}"));
+    RwLock::new(Arc::new(raw_data))
+}));
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::Path,
+        sync::{Arc, RwLock},
+    };
+
+    use cached::once_cell::sync::Lazy;
+    use color_eyre::eyre::Result;
+
+    use crate::data_types::{CurrentRawData, RawData};
+
+    #[test]
+    fn embedded_raw_data_is_valid() -> Result<()> {
+        RawData::embedded()?;
+        Ok(())
+    }
+
+    #[test]
+    fn raw_data_falls_back_to_embedded() -> Result<()> {
+        assert!(RawData::from_file(Path::new("nonexistent")).is_err());
+        assert!(RawData::from_slice(b"corrupt").is_err());
+        let raw_data = RawData::load(Some(Path::new("nonexistent")))?;
+        assert!(!raw_data.waypoints.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn current_raw_data_frees_replaced() -> Result<()> {
+        let current = CurrentRawData(Lazy::new(|| {
+            RwLock::new(Arc::new(
+                RawData::embedded().unwrap_or_else(|err| panic!("{err}")),
+            ))
+        }));
+        let old = current.get();
+        current.set(RawData {
+            air_facilities: vec![],
+            ..(*old).to_owned()
+        });
+        assert!(current.get().air_facilities.is_empty(), "Raw data not set");
+        assert!(!old.air_facilities.is_empty(), "Replaced raw data changed");
+        assert_eq!(Arc::strong_count(&old), 1, "Replaced raw data still held");
+        Ok(())
+    }
+}
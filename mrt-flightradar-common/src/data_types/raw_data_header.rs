@@ -0,0 +1,130 @@
+use std::{
+    io::Cursor,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+/// Start of every `raw_data` file with a header. Files without it are treated as version 0
+pub const MAGIC: &[u8; 8] = b"MRTFRRAW";
+/// Version written by [`RawDataHeader::new`]. Bump this and add a migration to
+/// [`RawDataHeader::decode`] whenever the layout of `RawData` changes
+pub const FORMAT_VERSION: u32 = 3;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawDataHeader {
+    pub version: u32,
+    /// Seconds since the Unix epoch
+    pub generated_at: u64,
+    /// Where the data came from, eg. sheet URLs and override files
+    pub sources: Vec<SmolStr>,
+    /// FNV-1a hash of the body
+    pub hash: u64,
+}
+
+/// 64-bit FNV-1a, which is stable across platforms and Rust versions unlike `DefaultHasher`
+#[must_use]
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl RawDataHeader {
+    #[must_use]
+    pub fn new(body: &[u8], sources: Vec<SmolStr>) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            generated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            sources,
+            hash: fnv1a(body),
+        }
+    }
+
+    pub fn encode(&self, body: &[u8]) -> Result<Vec<u8>> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.append(&mut rmp_serde::to_vec(self)?);
+        bytes.extend_from_slice(body);
+        Ok(bytes)
+    }
+
+    /// Splits a `raw_data` file into its header and body, checking that the body can be read.
+    /// Files from before headers were introduced have no header
+    pub fn decode(bytes: &[u8]) -> Result<(Option<Self>, &[u8])> {
+        let Some(rest) = bytes.strip_prefix(MAGIC) else {
+            return Ok((None, bytes));
+        };
+        let mut cursor = Cursor::new(rest);
+        let header: Self = rmp_serde::from_read(&mut cursor)
+            .map_err(|err| eyre!("Invalid raw data header: {err}"))?;
+        let body = &rest[usize::try_from(cursor.position())?..];
+
+        match header.version {
+            // version 0 is headerless, and versions 1 to 3 only added fields that default:
+            // `Airway::name`, `AirFacility` names and airport reference points
+            0..=3 => {}
+            v => {
+                return Err(eyre!(
+                    "Raw data is format version {v}, but only up to {FORMAT_VERSION} is supported. Update to read it"
+                ))
+            }
+        }
+        let hash = fnv1a(body);
+        if hash != header.hash {
+            return Err(eyre!(
+                "Raw data is corrupt: expected hash {:016x}, got {hash:016x}",
+                header.hash
+            ));
+        }
+        Ok((Some(header), body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use crate::data_types::{
+        raw_data_header::{fnv1a, RawDataHeader, FORMAT_VERSION},
+        RawData,
+    };
+
+    #[test]
+    fn fnv1a_known_values() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn raw_data_round_trip() -> Result<()> {
+        let raw_data = RawData::embedded()?;
+        let bytes = raw_data.to_vec(vec!["test".into()])?;
+        let (header, decoded) = RawData::from_slice_with_header(&bytes)?;
+        let header = header.unwrap();
+
+        assert_eq!(header.version, FORMAT_VERSION);
+        assert_eq!(header.sources, ["test"]);
+        assert_eq!(decoded.waypoints, raw_data.waypoints);
+        Ok(())
+    }
+
+    #[test]
+    fn raw_data_corrupt() -> Result<()> {
+        let mut bytes = RawData::embedded()?.to_vec(vec![])?;
+        *bytes.last_mut().unwrap() ^= 1;
+        assert!(RawData::from_slice(&bytes).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn raw_data_newer_version() -> Result<()> {
+        let mut header = RawDataHeader::new(b"", vec![]);
+        header.version = FORMAT_VERSION + 1;
+        assert!(RawDataHeader::decode(&header.encode(b"")?).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+};
+
+use chrono::Weekday;
+use smol_str::SmolStr;
+
+use crate::data_types::{
+    time::Time,
+    timetable::{AirlineTimetable, AirportCode, Flight},
+};
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// A problem with the flights of one aircraft, referring to flights by their index in the timetable
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RotationIssue {
+    /// `next` departs from somewhere other than where `prev` arrived
+    Teleport {
+        registry: SmolStr,
+        prev: usize,
+        next: usize,
+        arrived: AirportCode,
+        departs: AirportCode,
+    },
+    /// `next` departs before `prev` arrives
+    Overlap {
+        registry: SmolStr,
+        prev: usize,
+        next: usize,
+    },
+    /// Less time on the ground at `airport` than the minimum turnaround, between two flights or
+    /// at a stop of one flight if `prev` and `next` are the same
+    ShortTurnaround {
+        registry: SmolStr,
+        prev: usize,
+        next: usize,
+        airport: AirportCode,
+        minutes: i32,
+    },
+}
+
+impl Display for RotationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Teleport {
+                registry,
+                prev,
+                next,
+                arrived,
+                departs,
+            } => write!(
+                f,
+                "{registry}: Flight {next} departs from {departs}, but flight {prev} arrived at {arrived}"
+            ),
+            Self::Overlap {
+                registry,
+                prev,
+                next,
+            } => write!(
+                f,
+                "{registry}: Flight {next} departs before flight {prev} arrives"
+            ),
+            Self::ShortTurnaround {
+                registry,
+                prev,
+                next,
+                airport,
+                minutes,
+            } if prev == next => write!(
+                f,
+                "{registry}: Flight {prev} only has {minutes} minute(s) on the ground at {airport}"
+            ),
+            Self::ShortTurnaround {
+                registry,
+                prev,
+                next,
+                airport,
+                minutes,
+            } => write!(
+                f,
+                "{registry}: Only {minutes} minute(s) at {airport} between flights {prev} and {next}"
+            ),
+        }
+    }
+}
+
+/// Arrival of a flight at its last airport
+fn arrival(flight: &Flight) -> Option<(&AirportCode, Time)> {
+    let last = flight.segments.last()?;
+    Some((&last.airport, last.arrive_time.unwrap_or(last.depart_time)))
+}
+
+/// Chains the flights of each registry by departure time, separately for each day of the week
+/// they operate on, and finds the places where the aircraft couldn't fly them in that order.
+/// Flights without a registry are skipped, and rotations aren't followed past midnight
+#[must_use]
+pub fn check_rotations(timetable: &AirlineTimetable, min_turnaround: i32) -> Vec<RotationIssue> {
+    let mut registries = BTreeMap::<&SmolStr, Vec<usize>>::new();
+    for (i, flight) in timetable.flights.iter().enumerate() {
+        if !flight.registry.is_empty() && !flight.segments.is_empty() {
+            registries.entry(&flight.registry).or_default().push(i);
+        }
+    }
+
+    let mut issues = BTreeSet::new();
+    for (registry, flights) in registries {
+        let mut pairs = BTreeSet::new();
+        for day in WEEKDAYS {
+            let rotation = flights
+                .iter()
+                .copied()
+                .filter(|i| timetable.flights[*i].days.contains(day))
+                .map(|i| (timetable.flights[i].segments[0].depart_time, i))
+                .collect::<BTreeSet<_>>();
+            for ((_, prev), (_, next)) in rotation.iter().zip(rotation.iter().skip(1)) {
+                pairs.insert((*prev, *next));
+            }
+        }
+
+        for (prev, next) in pairs {
+            let (Some((arrived, arrive_time)), Some(first)) = (
+                arrival(&timetable.flights[prev]),
+                timetable.flights[next].segments.first(),
+            ) else {
+                continue;
+            };
+            let minutes = first.depart_time.minutes_after(arrive_time);
+            if minutes < 0 {
+                issues.insert(RotationIssue::Overlap {
+                    registry: registry.to_owned(),
+                    prev,
+                    next,
+                });
+            } else if *arrived != first.airport {
+                issues.insert(RotationIssue::Teleport {
+                    registry: registry.to_owned(),
+                    prev,
+                    next,
+                    arrived: arrived.to_owned(),
+                    departs: first.airport.to_owned(),
+                });
+            } else if minutes < min_turnaround {
+                issues.insert(RotationIssue::ShortTurnaround {
+                    registry: registry.to_owned(),
+                    prev,
+                    next,
+                    airport: arrived.to_owned(),
+                    minutes,
+                });
+            }
+        }
+
+        for i in flights {
+            let segments = &timetable.flights[i].segments;
+            for seg in segments
+                .iter()
+                .skip(1)
+                .take(segments.len().saturating_sub(2))
+            {
+                let Some(arrive_time) = seg.arrive_time else {
+                    continue;
+                };
+                let minutes = seg.depart_time.minutes_after(arrive_time);
+                if minutes < min_turnaround {
+                    issues.insert(RotationIssue::ShortTurnaround {
+                        registry: registry.to_owned(),
+                        prev: i,
+                        next: i,
+                        airport: seg.airport.to_owned(),
+                        minutes,
+                    });
+                }
+            }
+        }
+    }
+    issues.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use crate::data_types::{rotation::check_rotations, timetable::AirlineTimetable};
+
+    #[test]
+    fn rotation_check() -> Result<()> {
+        let timetable = AirlineTimetable::from_string(
+            r#"
+"Test",REG;AB1,AAA,0800;AB1,BBB,0900
+"Test",REG;AB2,BBB,0910;AB2,CCC,1000,0950;AB2,DDD,1100
+"Test",REG;AB3,EEE,1200;AB3,AAA,1300
+"Test",REG;AB4,AAA,1230;AB4,BBB,1400|days=1......
+"Test",REG;AB5,AAA,1330;AB5,BBB,1400|days=.2.....
+"Test",;AB6,ZZZ,0000;AB6,YYY,0100
+            "#
+            .trim(),
+            "Test".into(),
+        )?;
+        let issues = check_rotations(&timetable, 15)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            issues,
+            [
+                "REG: Flight 2 departs from EEE, but flight 1 arrived at DDD",
+                "REG: Flight 3 departs before flight 2 arrives",
+                "REG: Only 10 minute(s) at BBB between flights 0 and 1",
+                "REG: Flight 1 only has 10 minute(s) on the ground at CCC",
+            ]
+        );
+        Ok(())
+    }
+}
@@ -1,63 +1,189 @@
 use std::{
     fmt::{Display, Formatter},
-    ops::Add,
+    ops::{Add, Sub},
     str::FromStr,
 };
 
+use chrono::Duration;
 use color_eyre::eyre::{eyre, Error};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Time {
-    h: u8,
-    m: u8,
-}
+use crate::flight_route::types::path::MAX_SPEED;
+
+/// Timetable seconds per second of a simulated flight. Flights cruise at [`MAX_SPEED`] blocks a
+/// second, so this has them cover 5000 blocks a timetable hour like the editor's original estimate
+pub const TIME_SCALE: f32 = 3600.0 / (5000.0 / MAX_SPEED);
+
+pub const MINUTES_PER_DAY: i32 = 24 * 60;
+
+/// Time in a timetable, with a day offset for times on the days after a flight's first departure.
+/// Written like `2330`, or `0100+1` for the next day
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Time(u32);
 impl Display for Time {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:02}{:02}", self.h, self.m)
+        let minutes = self.0 % MINUTES_PER_DAY.unsigned_abs();
+        write!(f, "{:02}{:02}", minutes / 60, minutes % 60)?;
+        if self.day() != 0 {
+            write!(f, "+{}", self.day())?;
+        }
+        Ok(())
+    }
+}
+impl From<Time> for String {
+    fn from(time: Time) -> Self {
+        time.to_string()
+    }
+}
+impl TryFrom<String> for Time {
+    type Error = Error;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
     }
 }
 impl Add<f32> for Time {
     type Output = Self;
-    fn add(mut self, rhs: f32) -> Self::Output {
-        let h = rhs.floor() as u8;
-        let m = ((rhs - rhs.floor()) * 60.0).round() as u8;
-        self.h += h;
-        self.m += m;
-        self.simplified()
+    /// Adds a number of hours, rounded to the minute
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: f32) -> Self::Output {
+        self.add_minutes((rhs * 60.0).round() as i32)
+    }
+}
+impl Sub for Time {
+    type Output = Duration;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Duration::minutes(i64::from(self.0) - i64::from(rhs.0))
+    }
+}
+impl From<Time> for Duration {
+    /// Time since midnight on the first day
+    fn from(time: Time) -> Self {
+        Self::minutes(time.0.into())
+    }
+}
+impl TryFrom<Duration> for Time {
+    type Error = Error;
+    /// Time after midnight on the first day, rounded down to the minute
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        u32::try_from(duration.num_minutes())
+            .map(Self)
+            .map_err(|err| eyre!("Invalid time `{duration}`: {err}"))
     }
 }
 impl Time {
-    const fn simplified(mut self) -> Self {
-        while self.m >= 60 {
-            self.m -= 60;
-            self.h += 1;
-        }
-        while self.h >= 24 {
-            self.h -= 24;
-        }
-        self
+    /// Moves the time by a number of minutes, carrying over into the following days. Times before
+    /// the first day wrap around midnight into it
+    #[must_use]
+    pub fn add_minutes(self, minutes: i32) -> Self {
+        let total = i64::from(self.0) + i64::from(minutes);
+        let total = if total < 0 {
+            total.rem_euclid(MINUTES_PER_DAY.into())
+        } else {
+            total
+        };
+        Self(u32::try_from(total).unwrap_or(u32::MAX))
+    }
+    /// Adds a duration, or returns `None` if the time would be before the first day
+    #[must_use]
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        Self::try_from(Duration::from(self).checked_add(&duration)?).ok()
+    }
+    /// Subtracts a duration, or returns `None` if the time would be before the first day
+    #[must_use]
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        Self::try_from(Duration::from(self).checked_sub(&duration)?).ok()
+    }
+    /// Minutes from `earlier` to this time, which is negative if `earlier` is actually later
+    #[must_use]
+    pub fn minutes_after(self, earlier: Self) -> i32 {
+        i32::try_from((self - earlier).num_minutes()).unwrap_or(i32::MAX)
+    }
+    /// Number of days after the first day
+    #[must_use]
+    pub const fn day(self) -> u32 {
+        self.0 / MINUTES_PER_DAY.unsigned_abs()
+    }
+    /// The same time on the first day
+    #[must_use]
+    pub const fn time_of_day(self) -> Self {
+        Self(self.0 % MINUTES_PER_DAY.unsigned_abs())
     }
 }
 impl FromStr for Time {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = Regex::new(r"^([0-9]{2})([0-9]{2})$")?
+        let re = Regex::new(r"^([0-9]{2})([0-9]{2})(?:\+([0-9]+))?$")?
             .captures(s)
             .ok_or_else(|| eyre!("Invalid format"))?;
-        Ok(Self {
-            h: re
-                .get(1)
-                .ok_or_else(|| eyre!("No group 1"))?
-                .as_str()
-                .parse()?,
-            m: re
-                .get(2)
-                .ok_or_else(|| eyre!("No group 2"))?
-                .as_str()
-                .parse()?,
-        }
-        .simplified())
+        let h = re
+            .get(1)
+            .ok_or_else(|| eyre!("No group 1"))?
+            .as_str()
+            .parse::<u32>()?;
+        let m = re
+            .get(2)
+            .ok_or_else(|| eyre!("No group 2"))?
+            .as_str()
+            .parse::<u32>()?;
+        let day = re.get(3).map_or(Ok(0), |day| day.as_str().parse::<u32>())?;
+        Ok(Self(
+            day.checked_mul(MINUTES_PER_DAY.unsigned_abs())
+                .and_then(|day| day.checked_add(h * 60 + m))
+                .ok_or_else(|| eyre!("Day offset too large"))?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use color_eyre::eyre::Result;
+
+    use crate::data_types::time::Time;
+
+    #[test]
+    fn time_add_minutes() -> Result<()> {
+        let time = "2330".parse::<Time>()?;
+        assert_eq!(time.add_minutes(45), "0015+1".parse()?);
+        assert_eq!(time.add_minutes(-24 * 60 - 30), "2300".parse()?);
+        assert_eq!(time + 1.5, "0100+1".parse()?);
+        assert_eq!(time + 0.999, "0030+1".parse()?);
+        Ok(())
+    }
+
+    #[test]
+    fn time_minutes_after() -> Result<()> {
+        let time = "0015+1".parse::<Time>()?;
+        assert_eq!(time.minutes_after("2330".parse()?), 45);
+        assert_eq!(time.minutes_after("0015+1".parse()?), 0);
+        assert_eq!(time.minutes_after("0030+1".parse()?), -15);
+        Ok(())
+    }
+
+    #[test]
+    fn time_days() -> Result<()> {
+        let time = "0100+2".parse::<Time>()?;
+        assert_eq!(time.to_string(), "0100+2");
+        assert_eq!(time.day(), 2);
+        assert_eq!(time.time_of_day().to_string(), "0100");
+        assert!(time > "2330+1".parse()?, "Faulty ordering across midnight");
+        assert_eq!("2500".parse::<Time>()?.to_string(), "0100+1");
+        assert!("0100+".parse::<Time>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn time_duration() -> Result<()> {
+        let time = "0100+1".parse::<Time>()?;
+        assert_eq!(time - "2330".parse()?, Duration::minutes(90));
+        assert_eq!(Duration::from(time), Duration::hours(25));
+        assert_eq!(Time::try_from(Duration::hours(25))?, time);
+        assert_eq!(time.checked_sub(Duration::hours(2)), Some("2300".parse()?));
+        assert_eq!(time.checked_sub(Duration::hours(26)), None);
+        assert_eq!(time.checked_add(Duration::days(1)), Some("0100+2".parse()?));
+        Ok(())
     }
 }
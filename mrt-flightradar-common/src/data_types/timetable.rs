@@ -1,75 +1,390 @@
 use std::{
     fmt::{Display, Formatter},
-    fs,
-    path::PathBuf,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
 };
 
-use color_eyre::eyre::{eyre, Result};
+use chrono::{Datelike, Duration, NaiveDate};
+use color_eyre::eyre::{eyre, Error, Result};
 use itertools::Itertools;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
-use crate::data_types::time::Time;
+use crate::data_types::time::{Time, MINUTES_PER_DAY};
 
 pub type AirportCode = SmolStr;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Version of the `.fpln` format written by [`AirlineTimetable::to_file`], declared by a
+/// `#!fpln <version>` header. Version 1 files are plain flight rows, without the header,
+/// metadata or comments. Version 3 adds `|key=value` attributes after a flight's segments,
+/// version 4 an optional arrival time after a segment's departure time, and version 5 day offsets
+/// like `0100+1`. Times in older versions wrap around midnight, and are moved to the next day
+/// while reading whenever they are earlier than the time before
+pub const FORMAT_VERSION: u8 = 5;
+/// Format of validity dates, in files and in the editor
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+/// Patterns of the aircraft name, registry, and flight numbers and airport codes in a row
+const AIRCRAFT_PATTERN: &str = r#"[^"\r\n]+"#;
+const REGISTRY_PATTERN: &str = r"\w*";
+const CODE_PATTERN: &str = r"\w+";
+
+/// Writes a comment line. Comments starting with `!` would be read as metadata, so they and
+/// comments starting with the `\` escape itself get a `\` in front
+fn write_comment(f: &mut Formatter<'_>, comment: &str) -> std::fmt::Result {
+    if comment.starts_with(['!', '\\']) {
+        writeln!(f, "#\\{comment}")
+    } else {
+        writeln!(f, "#{comment}")
+    }
+}
+
+/// Days of the week a flight operates on, written like `12345..` where 1 is Monday
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Days(u8);
+
+impl Days {
+    pub const ALL: Self = Self(0b111_1111);
+
+    /// Number of days in a week the flight operates on
+    #[must_use]
+    pub const fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+    #[must_use]
+    pub const fn contains(self, day: chrono::Weekday) -> bool {
+        self.0 & (1 << day.num_days_from_monday()) != 0
+    }
+    /// The days `n` days later, eg. `1......` rotated by 1 is `.2.....`
+    #[must_use]
+    pub const fn rotated(self, n: i64) -> Self {
+        let n = n.rem_euclid(7);
+        Self(((self.0 << n) | (self.0 >> (7 - n))) & Self::ALL.0)
+    }
+}
+impl Default for Days {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+impl Display for Days {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for day in 0..7 {
+            if self.0 & (1 << day) == 0 {
+                write!(f, ".")?;
+            } else {
+                write!(f, "{}", day + 1)?;
+            }
+        }
+        Ok(())
+    }
+}
+impl From<Days> for String {
+    fn from(days: Days) -> Self {
+        days.to_string()
+    }
+}
+impl TryFrom<String> for Days {
+    type Error = Error;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+impl FromStr for Days {
+    type Err = Error;
+
+    /// Parses the days as digits from 1 (Monday) to 7 (Sunday), ignoring `.`, so both `12345..`
+    /// and `135` work
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = 0;
+        for c in s.chars().filter(|c| *c != '.') {
+            match c.to_digit(10) {
+                Some(day @ 1..=7) => days |= 1 << (day - 1),
+                _ => {
+                    return Err(eyre!(
+                        "Invalid day `{c}`, expected 1 (Monday) to 7 (Sunday)"
+                    ))
+                }
+            }
+        }
+        if days == 0 {
+            return Err(eyre!("A flight must operate on at least one day"));
+        }
+        Ok(Self(days))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AirlineTimetable {
+    /// From the `#!name` metadata line, otherwise the file name
     pub name: SmolStr,
+    /// Comments that aren't directly above a flight, which are written after the header
+    #[serde(default)]
+    pub comments: Vec<SmolStr>,
     pub flights: Vec<Flight>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Flight {
     pub aircraft: SmolStr,
     pub registry: SmolStr,
     pub segments: Vec<FlightSegment>,
+    /// Comment lines directly above the flight, without the leading `#`
+    #[serde(default)]
+    pub comments: Vec<SmolStr>,
+    #[serde(default)]
+    pub days: Days,
+    /// First date the flight operates on, if it doesn't always
+    #[serde(default)]
+    pub valid_from: Option<NaiveDate>,
+    /// Last date the flight operates on, if it doesn't always
+    #[serde(default)]
+    pub valid_to: Option<NaiveDate>,
+}
+
+impl Flight {
+    /// Minutes from the departure of segment `leg` to the arrival at the next segment's airport
+    #[must_use]
+    pub fn block_time(&self, leg: usize) -> Option<i32> {
+        let (seg, next) = (self.segments.get(leg)?, self.segments.get(leg + 1)?);
+        Some(
+            next.arrive_time
+                .unwrap_or(next.depart_time)
+                .minutes_after(seg.depart_time),
+        )
+    }
+
+    /// Moves every time by a number of minutes. If the first departure moves to another day, the
+    /// flight's days and validity dates move with it, so its times stay on the first day
+    pub fn shift(&mut self, minutes: i32) {
+        let Some(first) = self.segments.first().map(|seg| seg.depart_time) else {
+            return;
+        };
+        let days = (Duration::from(first) + Duration::minutes(minutes.into()))
+            .num_minutes()
+            .div_euclid(MINUTES_PER_DAY.into());
+        let minutes = minutes - i32::try_from(days).unwrap_or_default() * MINUTES_PER_DAY;
+        for seg in &mut self.segments {
+            seg.depart_time = seg.depart_time.add_minutes(minutes);
+            seg.arrive_time = seg.arrive_time.map(|t| t.add_minutes(minutes));
+        }
+        if days != 0 {
+            self.days = self.days.rotated(days);
+            self.valid_from = self.valid_from.map(|d| d + Duration::days(days));
+            self.valid_to = self.valid_to.map(|d| d + Duration::days(days));
+        }
+    }
+
+    /// Moves times of files written before day offsets to the next day when they are earlier
+    /// than the previous departure
+    fn infer_days(&mut self) {
+        let mut prev: Option<Time> = None;
+        let mut days = 0;
+        for seg in &mut self.segments {
+            for time in seg.arrive_time.iter_mut().chain([&mut seg.depart_time]) {
+                *time = time.add_minutes(days * MINUTES_PER_DAY);
+                while prev.is_some_and(|prev| *time < prev) {
+                    *time = time.add_minutes(MINUTES_PER_DAY);
+                    days += 1;
+                }
+            }
+            prev = Some(seg.depart_time);
+        }
+    }
+
+    /// Checks that the aircraft, registry, flight numbers and airports can be read back from a
+    /// file, that the flight stops operating after it starts, that departures are in order, and
+    /// that every arrival is after the previous departure and no later than the next
+    pub fn validate(&self) -> Result<()> {
+        let matches = |pattern: &str, s: &str| -> Result<bool> {
+            Ok(Regex::new(&format!("^(?:{pattern})$"))?.is_match(s))
+        };
+        if !matches(AIRCRAFT_PATTERN, &self.aircraft)? {
+            return Err(eyre!(
+                "Invalid aircraft `{}`, which cannot be empty or contain quotes",
+                self.aircraft
+            ));
+        }
+        if !matches(REGISTRY_PATTERN, &self.registry)? {
+            return Err(eyre!(
+                "Invalid registry `{}`, which can only have letters, digits and `_`",
+                self.registry
+            ));
+        }
+        for (i, seg) in self.segments.iter().enumerate() {
+            for (field, value) in [("flight number", &seg.flight_no), ("airport", &seg.airport)] {
+                if !matches(CODE_PATTERN, value)? {
+                    return Err(eyre!(
+                        "Segment {} has an invalid {field} `{value}`, which can only have letters, digits and `_`",
+                        i + 1
+                    ));
+                }
+            }
+        }
+        if let (Some(from), Some(to)) = (self.valid_from, self.valid_to) {
+            if from > to {
+                return Err(eyre!(
+                    "Flight stops operating on {to}, before it starts on {from}"
+                ));
+            }
+        }
+        for (i, seg) in self.segments.iter().enumerate() {
+            let prev = i.checked_sub(1).and_then(|i| self.segments.get(i));
+            if let Some(prev) = prev.filter(|prev| seg.depart_time < prev.depart_time) {
+                return Err(eyre!(
+                    "Segment {} departs at {}, before the previous departure at {}. Times on later days are written like `0100+1`",
+                    i + 1,
+                    seg.depart_time,
+                    prev.depart_time
+                ));
+            }
+            let Some(arrive_time) = seg.arrive_time else {
+                continue;
+            };
+            let Some(prev) = prev else {
+                return Err(eyre!("Segment 1 cannot have an arrival time"));
+            };
+            if arrive_time <= prev.depart_time || arrive_time > seg.depart_time {
+                return Err(eyre!(
+                    "Segment {} arrives at {arrive_time}, which is not between the departures at {} and {}",
+                    i + 1,
+                    prev.depart_time,
+                    seg.depart_time
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn operates_on(&self, date: NaiveDate) -> bool {
+        self.days.contains(date.weekday())
+            && self.valid_from.is_none_or(|from| from <= date)
+            && self.valid_to.is_none_or(|to| date <= to)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FlightSegment {
     pub flight_no: SmolStr,
     pub depart_time: Time,
     pub airport: AirportCode,
+    /// Scheduled arrival at the airport, from the previous segment. If not given, the flight is
+    /// taken to arrive at `depart_time`
+    #[serde(default)]
+    pub arrive_time: Option<Time>,
 }
 
 impl AirlineTimetable {
+    /// Validates every flight with [`Flight::validate`], naming the first invalid one by its index
+    pub fn validate_flights(flights: &[Flight]) -> Result<()> {
+        for (i, flight) in flights.iter().enumerate() {
+            flight
+                .validate()
+                .map_err(|err| eyre!("Flight {i}: {err}"))?;
+        }
+        Ok(())
+    }
+
+    /// Parses a timetable of any supported format version. `name` is used if the file doesn't
+    /// have a `#!name` line
     pub fn from_string(file_cont: &str, name: SmolStr) -> Result<Self> {
-        let flights = file_cont
-            .split('\n')
-            .filter(|a| !a.is_empty())
-            .map(|row| {
-                let row_re = Regex::new(r#"^"([^"]+)",(\w*);(.*)$"#)?
-                    .captures(row)
-                    .ok_or_else(|| eyre!("Invalid syntax"))?;
-                let aircraft = row_re.get(1).unwrap().as_str();
-                let registry = row_re.get(2).unwrap().as_str();
-                let segments = row_re
-                    .get(3)
-                    .unwrap()
-                    .as_str()
-                    .trim()
-                    .split(';')
-                    .map(|seg| {
-                        let seg_re = Regex::new(r"^(\w+),(\w+),(\d+)$")?
-                            .captures(seg)
-                            .ok_or_else(|| eyre!("Invalid syntax"))?;
-                        Ok(FlightSegment {
-                            flight_no: seg_re.get(1).unwrap().as_str().into(),
-                            airport: seg_re.get(2).unwrap().as_str().to_uppercase().into(),
-                            depart_time: seg_re.get(3).unwrap().as_str().parse()?,
-                        })
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-                Ok(Flight {
-                    aircraft: aircraft.into(),
-                    registry: registry.into(),
-                    segments,
+        let mut timetable = Self {
+            name,
+            comments: vec![],
+            flights: vec![],
+        };
+        let mut lines = file_cont.lines().enumerate().peekable();
+        let mut version = 1;
+        if let Some(header) = lines.peek().and_then(|(_, l)| l.strip_prefix("#!fpln ")) {
+            version = header
+                .trim()
+                .parse::<u8>()
+                .ok()
+                .filter(|v| (1..=FORMAT_VERSION).contains(v))
+                .ok_or_else(|| eyre!("Unsupported format version `{}`", header.trim()))?;
+            lines.next();
+        }
+
+        let mut comments = vec![];
+        for (i, line) in lines {
+            if let Some(metadata) = line.strip_prefix("#!") {
+                let (key, value) = metadata.split_once(' ').unwrap_or((metadata, ""));
+                match key {
+                    "name" => timetable.name = value.trim().into(),
+                    _ => return Err(eyre!("Line {}: Unknown metadata `{key}`", i + 1)),
+                }
+            } else if let Some(comment) = line.strip_prefix('#') {
+                comments.push(comment.strip_prefix('\\').unwrap_or(comment).into());
+            } else if line.trim().is_empty() {
+                timetable.comments.append(&mut comments);
+            } else {
+                let mut flight = Self::parse_flight(line, version)
+                    .map_err(|err| eyre!("Line {}: {err}", i + 1))?;
+                flight.comments = std::mem::take(&mut comments);
+                timetable.flights.push(flight);
+            }
+        }
+        timetable.comments.append(&mut comments);
+
+        Ok(timetable)
+    }
+    fn parse_flight(row: &str, version: u8) -> Result<Flight> {
+        let row_re = Regex::new(&format!(
+            r#"^"({AIRCRAFT_PATTERN})",({REGISTRY_PATTERN});(.*)$"#
+        ))?
+        .captures(row)
+        .ok_or_else(|| eyre!("Invalid syntax"))?;
+        let aircraft = row_re.get(1).unwrap().as_str();
+        let registry = row_re.get(2).unwrap().as_str();
+        let mut parts = row_re.get(3).unwrap().as_str().trim().split('|');
+        let segments = parts
+            .next()
+            .unwrap_or_default()
+            // a flight without segments is written with nothing after the registry
+            .split_terminator(';')
+            .map(|seg| {
+                let seg_re = Regex::new(&format!(
+                    r"^({CODE_PATTERN}),({CODE_PATTERN}),(\d+(?:\+\d+)?)(?:,(\d+(?:\+\d+)?))?$"
+                ))?
+                .captures(seg)
+                .ok_or_else(|| eyre!("Invalid syntax"))?;
+                Ok(FlightSegment {
+                    flight_no: seg_re.get(1).unwrap().as_str().into(),
+                    airport: seg_re.get(2).unwrap().as_str().to_uppercase().into(),
+                    depart_time: seg_re.get(3).unwrap().as_str().parse()?,
+                    arrive_time: seg_re.get(4).map(|t| t.as_str().parse()).transpose()?,
                 })
             })
             .collect::<Result<Vec<_>>>()?;
-
-        Ok(Self { name, flights })
+        let mut flight = Flight {
+            aircraft: aircraft.into(),
+            registry: registry.into(),
+            segments,
+            comments: vec![],
+            days: Days::ALL,
+            valid_from: None,
+            valid_to: None,
+        };
+        for attribute in parts {
+            let (key, value) = attribute
+                .split_once('=')
+                .ok_or_else(|| eyre!("Invalid attribute `{attribute}`"))?;
+            match key {
+                "days" => flight.days = value.parse()?,
+                "from" => flight.valid_from = Some(NaiveDate::parse_from_str(value, DATE_FORMAT)?),
+                "to" => flight.valid_to = Some(NaiveDate::parse_from_str(value, DATE_FORMAT)?),
+                _ => return Err(eyre!("Unknown attribute `{key}`")),
+            }
+        }
+        if version < 5 {
+            flight.infer_days();
+        }
+        flight.validate()?;
+        Ok(flight)
     }
     pub fn from_file(file: PathBuf) -> Result<Self> {
         let name = file
@@ -78,17 +393,46 @@ impl AirlineTimetable {
         let file_cont = fs::read_to_string(file)?;
         Self::from_string(&file_cont, name)
     }
-    pub fn to_file(&self, mut directory: PathBuf) -> Result<()> {
-        directory.push(format!("{}.fpln", self.name));
-
-        fs::write(directory, self.to_string())?;
+    /// Writes to a temporary file next to `path` first, then renames it over `path`,
+    /// so a crash mid-write leaves the old file intact. Both the file and the rename are synced
+    /// to disk before returning
+    pub fn to_file(&self, path: &Path) -> Result<()> {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(self.to_string().as_bytes())?;
+        file.sync_all()?;
+        fs::rename(tmp, path)?;
+        // directories can only be opened to be synced on unix
+        #[cfg(unix)]
+        File::open(
+            path.parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new(".")),
+        )?
+        .sync_all()?;
         Ok(())
     }
 }
 impl Display for AirlineTimetable {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut flights = self.flights.iter().map(|flight| {
-            format!(
+        writeln!(f, "#!fpln {FORMAT_VERSION}")?;
+        writeln!(f, "#!name {}", self.name)?;
+        for comment in &self.comments {
+            write_comment(f, comment)?;
+        }
+        if !self.comments.is_empty() {
+            writeln!(f)?;
+        }
+        for (i, flight) in self.flights.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            for comment in &flight.comments {
+                write_comment(f, comment)?;
+            }
+            write!(
+                f,
                 r#""{}",{};{}"#,
                 flight.aircraft,
                 flight.registry,
@@ -97,9 +441,18 @@ impl Display for AirlineTimetable {
                     .iter()
                     .map(std::string::ToString::to_string)
                     .join(";")
-            )
-        });
-        write!(f, "{}", flights.join("\n"))
+            )?;
+            if flight.days != Days::ALL {
+                write!(f, "|days={}", flight.days)?;
+            }
+            if let Some(from) = flight.valid_from {
+                write!(f, "|from={}", from.format(DATE_FORMAT))?;
+            }
+            if let Some(to) = flight.valid_to {
+                write!(f, "|to={}", to.format(DATE_FORMAT))?;
+            }
+        }
+        Ok(())
     }
 }
 impl Display for FlightSegment {
@@ -108,26 +461,206 @@ impl Display for FlightSegment {
             f,
             "{},{},{}",
             self.flight_no, self.airport, self.depart_time
-        )
+        )?;
+        if let Some(arrive_time) = self.arrive_time {
+            write!(f, ",{arrive_time}")?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 pub mod tests {
+    use std::{env, fs};
+
+    use chrono::NaiveDate;
     use color_eyre::eyre::Result;
+    use itertools::Itertools;
 
-    use crate::data_types::timetable::AirlineTimetable;
+    use crate::data_types::timetable::{AirlineTimetable, DATE_FORMAT};
 
     #[test]
     pub fn serde_airline_timetable() -> Result<()> {
         let raw = r#"
-"Test",REG;AB123,ABC,0000;CD456,DEF,0100
-"Test",REG;AB123,ABC,0000;CD456,DEF,0100
+#!fpln 5
+#!name Test Airline
+# Summer schedule
+
+# First rotation
+"Test",REG;AB123,ABC,0000;CD456,DEF,0100,0050
+"Test",REG;AB123,ABC,2330;CD456,DEF,0100+1,0045+1
+"Test",REG;AB123,ABC,0000;CD456,DEF,0100|days=1.3.5..|from=2026-06-01|to=2026-08-31
         "#
         .trim()
         .to_owned();
-        let deserialised = AirlineTimetable::from_string(&raw, "Test Airline".into())?;
+        let deserialised = AirlineTimetable::from_string(&raw, "File Name".into())?;
+        assert_eq!(deserialised.name, "Test Airline");
+        assert_eq!(deserialised.comments, [" Summer schedule"]);
+        assert_eq!(deserialised.flights[0].comments, [" First rotation"]);
+        assert_eq!(deserialised.to_string(), raw);
+        Ok(())
+    }
+
+    #[test]
+    pub fn serde_escaped_comments() -> Result<()> {
+        let mut timetable =
+            AirlineTimetable::from_string(r#""Test",REG;AB123,ABC,0000"#, "Test Airline".into())?;
+        timetable.comments = vec!["!name Other".into(), r"\escaped".into()];
+        timetable.flights[0].comments = vec!["!check this".into()];
+        let raw = timetable.to_string();
+        assert!(
+            raw.contains("#\\!check this\n"),
+            "Comment not escaped: {raw}"
+        );
+        assert_eq!(
+            AirlineTimetable::from_string(&raw, "File Name".into())?,
+            timetable
+        );
+        Ok(())
+    }
+
+    #[test]
+    pub fn flight_operates_on() -> Result<()> {
+        let timetable = AirlineTimetable::from_string(
+            r#""Test",REG;AB123,ABC,0000|days=12345..|from=2026-06-01"#,
+            "Test".into(),
+        )?;
+        let flight = &timetable.flights[0];
+        let date = |d: &str| NaiveDate::parse_from_str(d, DATE_FORMAT);
+        assert!(flight.operates_on(date("2026-06-01")?), "Monday");
+        assert!(!flight.operates_on(date("2026-06-06")?), "Saturday");
+        assert!(!flight.operates_on(date("2026-05-25")?), "Before validity");
+        Ok(())
+    }
+
+    #[test]
+    pub fn flight_block_time() -> Result<()> {
+        let timetable = AirlineTimetable::from_string(
+            r#""Test",REG;AB123,ABC,2330;CD456,DEF,0100,0045;EF789,GHI,0200"#,
+            "Test".into(),
+        )?;
+        let flight = &timetable.flights[0];
+        assert_eq!(
+            flight.block_time(0),
+            Some(75),
+            "Faulty block time to arrival"
+        );
+        assert_eq!(
+            flight.block_time(1),
+            Some(60),
+            "Faulty block time to departure"
+        );
+        assert_eq!(
+            flight.block_time(2),
+            None,
+            "Faulty block time for last segment"
+        );
+        Ok(())
+    }
+
+    #[test]
+    pub fn flight_days() -> Result<()> {
+        let timetable = AirlineTimetable::from_string(
+            "#!fpln 4\n\"Test\",REG;AB123,ABC,2300;CD456,DEF,0100,0045;EF789,GHI,0030|days=1......",
+            "Test".into(),
+        )?;
+        let mut flight = timetable.flights[0].to_owned();
+        assert_eq!(
+            flight.segments.iter().map(ToString::to_string).join(";"),
+            "AB123,ABC,2300;CD456,DEF,0100+1,0045+1;EF789,GHI,0030+2",
+            "Faulty day inference"
+        );
+
+        flight.shift(90);
+        assert_eq!(
+            flight.segments[0].depart_time.to_string(),
+            "0030",
+            "Faulty shift"
+        );
+        assert_eq!(flight.segments[2].depart_time.to_string(), "0200+1");
+        assert_eq!(flight.days.to_string(), ".2.....", "Faulty day rotation");
+        flight.shift(-60);
+        assert_eq!(flight.segments[0].depart_time.to_string(), "2330");
+        assert_eq!(flight.days.to_string(), "1......", "Faulty day rotation");
+        Ok(())
+    }
+
+    #[test]
+    pub fn flight_validate_tokens() -> Result<()> {
+        let timetable =
+            AirlineTimetable::from_string(r#""Test",REG;AB123,ABC,0000"#, "Test".into())?;
+        let flight = &timetable.flights[0];
+        flight.validate()?;
+        let mut invalid = vec![flight.to_owned(); 4];
+        invalid[0].aircraft = "Test \"Jet\"".into();
+        invalid[1].registry = "RE-G".into();
+        invalid[2].segments[0].flight_no = "XY-123".into();
+        invalid[3].segments[0].airport = "".into();
+        for flight in invalid {
+            assert!(flight.validate().is_err(), "{flight:?} did not error");
+        }
+        Ok(())
+    }
+
+    #[test]
+    pub fn serde_airline_timetable_v1() -> Result<()> {
+        let raw = r#""Test",REG;AB123,ABC,0000;CD456,DEF,0100"#;
+        let deserialised = AirlineTimetable::from_string(raw, "Test Airline".into())?;
+        assert_eq!(deserialised.name, "Test Airline");
+        assert_eq!(
+            deserialised.to_string(),
+            format!("#!fpln 5\n#!name Test Airline\n{raw}")
+        );
+        Ok(())
+    }
+
+    #[test]
+    pub fn serde_flight_without_segments() -> Result<()> {
+        let raw = "#!fpln 5\n#!name Test Airline\n\"Test\",REG;\n\"Test\",REG;|days=1......";
+        let deserialised = AirlineTimetable::from_string(raw, "Test Airline".into())?;
+        assert!(deserialised.flights.iter().all(|f| f.segments.is_empty()));
+        AirlineTimetable::validate_flights(&deserialised.flights)?;
         assert_eq!(deserialised.to_string(), raw);
         Ok(())
     }
+
+    #[test]
+    pub fn serde_airline_timetable_errors() {
+        for raw in [
+            "#!fpln 6",
+            "#!fpln two",
+            "#!fpln 5\n\"Test\",REG;AB123,ABC,2330;CD456,DEF,0100",
+            "#!unknown value",
+            "\"Test\",REG;AB123,ABC",
+            "\"Test\",REG;AB123,ABC,0000|days=8",
+            "\"Test\",REG;AB123,ABC,0000|days=.......",
+            "\"Test\",REG;AB123,ABC,0000|from=2026-06-01|to=2026-05-01",
+            "\"Test\",REG;AB123,ABC,0000|gate=1",
+            "\"Test\",REG;AB123,ABC,0000,2350",
+            "\"Test\",REG;AB123,ABC,0000;CD456,DEF,0100,0110",
+            "\"Test\",REG;AB123,ABC,0000;CD456,DEF,0100,0000",
+        ] {
+            assert!(
+                AirlineTimetable::from_string(raw, "Test".into()).is_err(),
+                "`{raw}` did not error"
+            );
+        }
+    }
+
+    #[test]
+    pub fn airline_timetable_file() -> Result<()> {
+        let dir = env::temp_dir().join(format!("mrt-fr-timetable-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("renamed.fpln");
+        let timetable =
+            AirlineTimetable::from_string(r#""Test",REG;AB123,ABC,0000"#, "Test Airline".into())?;
+        timetable.to_file(&path)?;
+        let files = fs::read_dir(&dir)?.count();
+        let read = AirlineTimetable::from_file(path);
+        fs::remove_dir_all(&dir)?;
+
+        assert_eq!(files, 1, "Temporary file left behind");
+        assert_eq!(read?.flights, timetable.flights);
+        Ok(())
+    }
 }
@@ -4,7 +4,7 @@ use rand::Rng;
 use tracing::debug;
 
 use crate::{
-    data_types::{airport::Runway, vec::FromLoc, waypoint::Waypoint},
+    data_types::{airport::Runway, vec::FromLoc, waypoint::Waypoint, RawData},
     flight_route::{
         flight_path::get_flight_path, types::path::FlightPath, waypoint_route::get_waypoint_route,
     },
@@ -15,23 +15,17 @@ mod flight_path;
 pub mod types;
 mod waypoint_route;
 
+/// Routes between the runways over the waypoints and airways of `raw_data`, which should be the
+/// dataset the runways come from. The waypoints are moved slightly at random so that flights
+/// along the same airway don't overlap
 #[tracing::instrument(skip_all)]
-pub fn get_flight_route(
+pub fn get_flight_route<'a>(
+    raw_data: &'a RawData,
     start_runway: &Runway,
     end_runway: &Runway,
-) -> Result<(Vec<&'static Waypoint>, FlightPath)> {
-    let start_vec = FromLoc {
-        tail: start_runway.vec.tail,
-        vec: start_runway.vec.vec.normalize() * (500.0 + start_runway.vec.vec.length()),
-    };
-    let end_vec_vec = end_runway.vec.vec.normalize() * (500.0 + end_runway.vec.vec.length());
-    let end_vec = FromLoc {
-        tail: end_runway.vec.head() - end_vec_vec,
-        vec: end_vec_vec,
-    };
-    debug!(?start_vec, ?end_vec);
-
-    let (waypoints, mut positions) = get_waypoint_route(start_vec, end_vec)?;
+) -> Result<(Vec<&'a Waypoint>, FlightPath)> {
+    let (start_vec, end_vec) = approach_vecs(start_runway, end_runway);
+    let (waypoints, mut positions) = get_waypoint_route(raw_data, start_vec, end_vec)?;
 
     for pos in &mut positions {
         *pos += vec2(
@@ -45,3 +39,31 @@ pub fn get_flight_route(
         get_flight_path(start_vec, end_vec, positions, 100.0),
     ))
 }
+
+/// Path of [`get_flight_route`] without moving the waypoints, so the same runways always give
+/// the same path, eg. for estimating flight times
+#[tracing::instrument(skip_all)]
+pub fn get_estimated_route(
+    raw_data: &RawData,
+    start_runway: &Runway,
+    end_runway: &Runway,
+) -> Result<FlightPath> {
+    let (start_vec, end_vec) = approach_vecs(start_runway, end_runway);
+    let (_, positions) = get_waypoint_route(raw_data, start_vec, end_vec)?;
+    Ok(get_flight_path(start_vec, end_vec, positions, 100.0))
+}
+
+/// Vectors the flight takes off along and lands along, extending the runways by 500 blocks
+fn approach_vecs(start_runway: &Runway, end_runway: &Runway) -> (FromLoc, FromLoc) {
+    let start_vec = FromLoc {
+        tail: start_runway.vec.tail,
+        vec: start_runway.vec.vec.normalize() * (500.0 + start_runway.vec.vec.length()),
+    };
+    let end_vec_vec = end_runway.vec.vec.normalize() * (500.0 + end_runway.vec.vec.length());
+    let end_vec = FromLoc {
+        tail: end_runway.vec.head() - end_vec_vec,
+        vec: end_vec_vec,
+    };
+    debug!(?start_vec, ?end_vec);
+    (start_vec, end_vec)
+}
@@ -8,14 +8,18 @@ use tracing::trace;
 use crate::data_types::{
     vec::{FromLoc, Pos},
     waypoint::Waypoint,
-    RAW_DATA,
+    RawData,
 };
 
-fn a_star(start: &'static Waypoint, end: &'static Waypoint) -> Option<Vec<&'static Waypoint>> {
-    let to_wp = |n: &SmolStr| RAW_DATA.waypoints.iter().find(|w| w.name == *n).unwrap();
+fn a_star<'a>(
+    raw_data: &'a RawData,
+    start: &'a Waypoint,
+    end: &'a Waypoint,
+) -> Option<Vec<&'a Waypoint>> {
+    let to_wp = |n: &SmolStr| raw_data.waypoints.iter().find(|w| w.name == *n).unwrap();
     let h = |n: &SmolStr| to_wp(n).coords.distance(end.coords);
     let neighbours = |n: &SmolStr| {
-        RAW_DATA
+        raw_data
             .airways
             .iter()
             .filter_map(|aw| {
@@ -67,22 +71,23 @@ fn a_star(start: &'static Waypoint, end: &'static Waypoint) -> Option<Vec<&'stat
 
 #[tracing::instrument(skip_all)]
 pub fn get_waypoint_route(
+    raw_data: &RawData,
     start: FromLoc,
     end: FromLoc,
-) -> Result<(Vec<&'static Waypoint>, Vec<Pos<Vec2>>)> {
-    let start_wp = RAW_DATA
+) -> Result<(Vec<&Waypoint>, Vec<Pos<Vec2>>)> {
+    let start_wp = raw_data
         .waypoints
         .iter()
         .min_by_key(|wp| start.head().distance(wp.coords) as u32)
         .ok_or_else(|| eyre!("No waypoints found"))?;
-    let end_wp = RAW_DATA
+    let end_wp = raw_data
         .waypoints
         .iter()
         .min_by_key(|wp| end.tail.distance(wp.coords) as u32)
         .ok_or_else(|| eyre!("No waypoints found"))?;
     trace!(?start_wp, ?end_wp);
 
-    let waypoints = a_star(start_wp, end_wp).ok_or_else(|| eyre!("No route found"))?;
+    let waypoints = a_star(raw_data, start_wp, end_wp).ok_or_else(|| eyre!("No route found"))?;
 
     Ok((
         waypoints.to_owned(),
@@ -0,0 +1,80 @@
+use common::data_types::{airport::AirFacility, airport_names::AirportNames};
+use smol_str::SmolStr;
+use tracing::debug;
+
+/// Names every air facility listed in `airport_names`. Returns a line for every name that couldn't
+/// be matched to an air facility, either because it has no code or because no air facility has
+/// its code, and for every air facility left without a name
+#[tracing::instrument(skip_all)]
+pub fn merge_airport_names(
+    air_facilities: &mut [AirFacility],
+    airport_names: &AirportNames,
+) -> Vec<String> {
+    let mut report = airport_names
+        .without_code()
+        .map(|name| format!("`{name}` has no code"))
+        .collect::<Vec<_>>();
+    report.extend(
+        airport_names
+            .not_in_raw_data(air_facilities)
+            .into_iter()
+            .map(|code| {
+                format!(
+                    "{code} ({}) is not an air facility",
+                    airport_names.name(code).map_or("", SmolStr::as_str)
+                )
+            }),
+    );
+
+    for af in air_facilities.iter_mut() {
+        if let Some(name) = airport_names.name(af.code()) {
+            debug!(code = af.code().as_str(), ?name, "Naming air facility");
+            *af.name_mut() = Some(name.to_owned());
+        }
+    }
+    report.extend(
+        airport_names
+            .unnamed(air_facilities)
+            .into_iter()
+            .map(|code| format!("Air facility {code} has no name")),
+    );
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::data_types::{airport::AirFacility, airport_names::AirportNames};
+    use glam::vec2;
+    use smol_str::SmolStr;
+
+    use crate::airport_names::merge_airport_names;
+
+    #[test]
+    fn airport_names_merged() -> Result<()> {
+        let mut air_facilities = ["ABC", "DEF"]
+            .map(|code| AirFacility::Heliport {
+                code: code.into(),
+                pad_coord: vec2(0.0, 0.0),
+                name: None,
+            })
+            .to_vec();
+        let airport_names =
+            AirportNames::from_string("Alpha Heliport\tABC\nNo Code Airfield\nZulu Airport\tZZZ")?;
+        let report = merge_airport_names(&mut air_facilities, &airport_names);
+        assert_eq!(
+            air_facilities[0].name().map(SmolStr::as_str),
+            Some("Alpha Heliport")
+        );
+        assert_eq!(air_facilities[1].name(), None);
+        assert_eq!(
+            report,
+            [
+                "`No Code Airfield` has no code",
+                "ZZZ (Zulu Airport) is not an air facility",
+                "Air facility DEF has no name",
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::{airway::Airway, waypoint::Waypoint};
+use smol_str::SmolStr;
+use tracing::{debug, info};
+
+#[derive(Clone, Debug, Default)]
+pub struct AirwayOverrides {
+    pub forced: Vec<Airway>,
+    pub forbidden: Vec<Airway>,
+    pub named: Vec<(SmolStr, Vec<SmolStr>)>,
+}
+
+impl AirwayOverrides {
+    fn waypoint_names(&self) -> impl Iterator<Item = &SmolStr> {
+        self.forced
+            .iter()
+            .chain(&self.forbidden)
+            .flat_map(|aw| [&aw.waypoint1, &aw.waypoint2])
+            .chain(self.named.iter().flat_map(|(_, wps)| wps))
+    }
+}
+
+fn airway(waypoint1: &str, waypoint2: &str, name: Option<&SmolStr>) -> Airway {
+    Airway {
+        waypoint1: waypoint1.into(),
+        waypoint2: waypoint2.into(),
+        name: name.cloned(),
+    }
+}
+
+/// Parses the overrides file. Each non-empty line that isn't a `#` comment is one of:
+///
+/// - `+ <waypoint1> <waypoint2>` to force an airway between the two waypoints
+/// - `- <waypoint1> <waypoint2>` to forbid the airway between the two waypoints
+/// - `= <ident> <waypoint1> <waypoint2> [<waypoint3>...]` to name an airway through the waypoints
+#[tracing::instrument(skip_all)]
+pub fn get_airway_overrides(str: &str) -> Result<AirwayOverrides> {
+    let mut overrides = AirwayOverrides::default();
+    for (i, line) in str.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let mut cells = line.split_whitespace();
+        let Some(op) = cells.next() else {
+            continue;
+        };
+        let cells = cells.collect::<Vec<_>>();
+        match (op, &*cells) {
+            ("+", [wp1, wp2]) => overrides.forced.push(airway(wp1, wp2, None)),
+            ("-", [wp1, wp2]) => overrides.forbidden.push(airway(wp1, wp2, None)),
+            ("=", [name, wps @ ..]) if wps.len() >= 2 => overrides
+                .named
+                .push(((*name).into(), wps.iter().map(|a| (*a).into()).collect())),
+            _ => return Err(eyre!("Invalid airway override on line {}: `{line}`", i + 1)),
+        }
+    }
+    debug!(?overrides);
+    Ok(overrides)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn apply_airway_overrides(
+    airways: &mut Vec<Airway>,
+    overrides: &AirwayOverrides,
+    waypoints: &[Waypoint],
+) -> Result<()> {
+    if let Some(name) = overrides
+        .waypoint_names()
+        .find(|name| !waypoints.iter().any(|w| w.name == **name))
+    {
+        return Err(eyre!("Unknown waypoint `{name}` in airway overrides"));
+    }
+
+    let named = overrides
+        .named
+        .iter()
+        .flat_map(|(name, wps)| wps.windows(2).map(|w| airway(&w[0], &w[1], Some(name))))
+        .collect::<Vec<_>>();
+    if let Some(aw) = named
+        .iter()
+        .chain(&overrides.forced)
+        .find(|aw| overrides.forbidden.contains(aw))
+    {
+        return Err(eyre!(
+            "Airway between `{}` and `{}` is both forbidden and forced",
+            aw.waypoint1,
+            aw.waypoint2
+        ));
+    }
+
+    let len = airways.len();
+    airways.retain(|aw| !overrides.forbidden.contains(aw));
+    info!(removed = len - airways.len(), "Removed forbidden airways");
+
+    for new in overrides.forced.iter().chain(&named) {
+        if let Some(existing) = airways.iter_mut().find(|aw| *aw == new) {
+            if new.name.is_some() {
+                existing.name = new.name.to_owned();
+            }
+        } else {
+            debug!(airway = ?new, "New forced airway");
+            airways.push(new.to_owned());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::data_types::{airway::Airway, waypoint::Waypoint};
+    use glam::vec2;
+
+    use crate::airway_overrides::{apply_airway_overrides, get_airway_overrides};
+
+    fn waypoints() -> Vec<Waypoint> {
+        ["AB", "CD", "EF", "GH"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| Waypoint {
+                name: name.into(),
+                coords: vec2(i as f32, 0.0),
+            })
+            .collect()
+    }
+
+    fn airways() -> Vec<Airway> {
+        vec![
+            Airway {
+                waypoint1: "AB".into(),
+                waypoint2: "CD".into(),
+                name: None,
+            },
+            Airway {
+                waypoint1: "CD".into(),
+                waypoint2: "EF".into(),
+                name: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn overrides_applied() -> Result<()> {
+        let overrides = get_airway_overrides(
+            "# comment\n\n+ AB GH\n- CD EF # trailing comment\n= A1 CD AB GH\n",
+        )?;
+        let mut airways = airways();
+        apply_airway_overrides(&mut airways, &overrides, &waypoints())?;
+
+        assert_eq!(airways.len(), 2, "Faulty airway count");
+        assert!(airways.iter().all(|aw| aw.name.as_deref() == Some("A1")));
+        assert!(airways
+            .iter()
+            .any(|aw| aw.connects(&"AB".into(), &"GH".into())));
+        Ok(())
+    }
+
+    #[test]
+    fn overrides_invalid_syntax() {
+        assert!(get_airway_overrides("+ AB").is_err());
+        assert!(get_airway_overrides("= A1 AB").is_err());
+        assert!(get_airway_overrides("? AB CD").is_err());
+    }
+
+    #[test]
+    fn overrides_unknown_waypoint() -> Result<()> {
+        let overrides = get_airway_overrides("+ AB ZZ")?;
+        assert!(apply_airway_overrides(&mut airways(), &overrides, &waypoints()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn overrides_conflict() -> Result<()> {
+        let overrides = get_airway_overrides("- AB CD\n= A1 CD AB")?;
+        assert!(apply_airway_overrides(&mut airways(), &overrides, &waypoints()).is_err());
+        Ok(())
+    }
+}
@@ -23,6 +23,7 @@ pub fn generate_airways(waypoints: &[Waypoint]) -> Vec<Airway> {
             let airway = Airway {
                 waypoint1: wp.name.to_owned(),
                 waypoint2: nw.name.to_owned(),
+                name: None,
             };
             if !airways.contains(&airway) {
                 debug!(?airway, "New airway");
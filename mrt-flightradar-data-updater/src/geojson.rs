@@ -0,0 +1,190 @@
+use common::data_types::{
+    airport::{AirFacility, PlaneFacilityType, Runway},
+    vec::Pos,
+    RawData,
+};
+use glam::Vec2;
+use serde_json::{json, Value};
+use smol_str::SmolStr;
+
+fn feature(geometry: Value, properties: Value) -> Value {
+    let mut feature = json!({ "type": "Feature" });
+    feature["geometry"] = geometry;
+    feature["properties"] = properties;
+    feature
+}
+
+fn point(coords: Pos<Vec2>, properties: Value) -> Value {
+    feature(
+        json!({ "type": "Point", "coordinates": [coords.x, coords.y] }),
+        properties,
+    )
+}
+
+fn line_string(coords: &[Pos<Vec2>], properties: Value) -> Value {
+    feature(
+        json!({
+            "type": "LineString",
+            "coordinates": coords.iter().map(|c| [c.x, c.y]).collect::<Vec<_>>(),
+        }),
+        properties,
+    )
+}
+
+fn facility_features(af: &AirFacility) -> Vec<Value> {
+    let (ty, runways): (_, &[Runway]) = match af {
+        AirFacility::Heliport { .. } => ("Heliport", &[]),
+        AirFacility::AirshipTerminal { .. } => ("Airship Terminal", &[]),
+        AirFacility::Airport { ty, runways, .. } => (
+            match ty {
+                PlaneFacilityType::Airport => "Airport",
+                PlaneFacilityType::Airfield => "Airfield",
+            },
+            runways,
+        ),
+    };
+    let mut features = af
+        .main_coord()
+        .map(|coords| {
+            point(
+                coords,
+                json!({
+                    "kind": "facility",
+                    "code": af.code().as_str(),
+                    "name": af.name().map(SmolStr::as_str),
+                    "type": ty,
+                }),
+            )
+        })
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    // every runway is stored once per direction, so only export the first of each pair
+    for (i, runway) in runways.iter().enumerate() {
+        let (dir1, dir2) = &runway.direction;
+        if runways[..i]
+            .iter()
+            .any(|r| r.direction.0 == *dir2 && r.direction.1 == *dir1)
+        {
+            continue;
+        }
+        features.push(line_string(
+            &[runway.vec.tail, runway.vec.head()],
+            json!({
+                "kind": "runway",
+                "code": af.code().as_str(),
+                "direction": format!("{dir1} - {dir2}"),
+                "width": runway.length,
+            }),
+        ));
+    }
+    features
+}
+
+/// Exports the whole dataset as a `GeoJSON` `FeatureCollection`.
+///
+/// Coordinates are in blocks with `y` pointing north, ie. the negative of the in-game `z`
+pub fn to_geojson(raw_data: &RawData) -> Value {
+    let waypoints = raw_data.waypoints.iter().map(|wp| {
+        point(
+            wp.coords,
+            json!({ "kind": "waypoint", "name": wp.name.as_str() }),
+        )
+    });
+    let airways = raw_data.airways.iter().filter_map(|aw| {
+        let wp1 = raw_data.waypoints.iter().find(|w| w.name == aw.waypoint1)?;
+        let wp2 = raw_data.waypoints.iter().find(|w| w.name == aw.waypoint2)?;
+        Some(line_string(
+            &[wp1.coords, wp2.coords],
+            json!({
+                "kind": "airway",
+                "name": aw.name.as_deref(),
+                "waypoint1": aw.waypoint1.as_str(),
+                "waypoint2": aw.waypoint2.as_str(),
+            }),
+        ))
+    });
+    let facilities = raw_data.air_facilities.iter().flat_map(facility_features);
+
+    json!({
+        "type": "FeatureCollection",
+        "features": waypoints.chain(airways).chain(facilities).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use common::data_types::{
+        airport::{AirFacility, PlaneFacilityType, Runway, RunwayWidth},
+        airway::Airway,
+        vec::FromLoc,
+        waypoint::Waypoint,
+        RawData,
+    };
+    use glam::vec2;
+    use smallvec::smallvec;
+
+    use crate::geojson::to_geojson;
+
+    #[test]
+    fn geojson_features() {
+        let (c1, c2) = (vec2(0.0, 0.0), vec2(100.0, 0.0));
+        let raw_data = RawData {
+            air_facilities: vec![
+                AirFacility::Heliport {
+                    code: "HEL".into(),
+                    pad_coord: c1,
+                    name: None,
+                },
+                AirFacility::Airport {
+                    code: "ABC".into(),
+                    ty: PlaneFacilityType::Airport,
+                    runways: smallvec![
+                        Runway {
+                            vec: FromLoc::new(c1, c2),
+                            direction: ("09".into(), "27".into()),
+                            length: RunwayWidth::Large,
+                        },
+                        Runway {
+                            vec: FromLoc::new(c2, c1),
+                            direction: ("27".into(), "09".into()),
+                            length: RunwayWidth::Large,
+                        },
+                    ],
+                    name: Some("Airport".into()),
+                    reference_point: None,
+                },
+            ],
+            waypoints: vec![
+                Waypoint {
+                    name: "AB".into(),
+                    coords: c1,
+                },
+                Waypoint {
+                    name: "CD".into(),
+                    coords: c2,
+                },
+            ],
+            airways: vec![Airway {
+                waypoint1: "AB".into(),
+                waypoint2: "CD".into(),
+                name: Some("A1".into()),
+            }],
+        };
+        let geojson = to_geojson(&raw_data);
+        let features = geojson["features"].as_array().unwrap();
+        let kinds = features
+            .iter()
+            .map(|f| f["properties"]["kind"].as_str().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            ["waypoint", "waypoint", "airway", "facility", "facility", "runway"]
+        );
+        assert_eq!(features[2]["properties"]["name"], "A1");
+        assert_eq!(features[4]["properties"]["name"], "Airport");
+        assert_eq!(features[5]["properties"]["direction"], "09 - 27");
+        assert_eq!(features[5]["properties"]["width"], "Large");
+    }
+}
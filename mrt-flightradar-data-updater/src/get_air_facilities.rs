@@ -8,30 +8,52 @@ use common::{
 };
 use itertools::Itertools;
 use smallvec::smallvec;
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
+
+const CODE_COLUMN: usize = 0;
+const TYPE_COLUMN: usize = 1;
+/// Header of the optional column with an airport reference point overriding the centroid of the
+/// runways, eg. `100 -200`. It's only read before [`COORDS_COLUMN`], and cells that are empty or
+/// aren't coordinates are ignored
+const REFERENCE_POINT_HEADER: &str = "Reference point";
+/// Pad of heliports and airship terminals, or the start of the runways of airports, which are
+/// each two coordinates, a direction like `09 - 27` and a width
+const COORDS_COLUMN: usize = 3;
 
 #[tracing::instrument]
 pub fn get_air_facilities(str: &str) -> Result<Vec<AirFacility>> {
-    from_csv(str)
-        .into_iter()
+    let rows = from_csv(str);
+    let reference_point_column = rows.first().and_then(|header| {
+        header
+            .iter()
+            .take(COORDS_COLUMN)
+            .position(|cell| cell.eq_ignore_ascii_case(REFERENCE_POINT_HEADER))
+    });
+    rows.into_iter()
         .skip(1)
         .map(|row| {
-            let code = row.first().ok_or_else(|| eyre!("No code"))?;
-            match row.get(1) {
+            let code = row.get(CODE_COLUMN).ok_or_else(|| eyre!("No code"))?;
+            match row.get(TYPE_COLUMN) {
                 Some(&"Heliport") => {
                     debug!(code, "Deserialising heliport");
-                    let pad_coord = row.get(3).ok_or_else(|| eyre!("No pad_coord"))?;
+                    let pad_coord = row
+                        .get(COORDS_COLUMN)
+                        .ok_or_else(|| eyre!("No pad_coord"))?;
                     Ok(AirFacility::Heliport {
                         code: (*code).into(),
                         pad_coord: coords_to_vec(pad_coord)?,
+                        name: None,
                     })
                 }
                 Some(&"Airship Terminal") => {
                     debug!(code, "Deserialising airship terminal");
-                    let pad_coord = row.get(3).ok_or_else(|| eyre!("No pad_coord"))?;
+                    let pad_coord = row
+                        .get(COORDS_COLUMN)
+                        .ok_or_else(|| eyre!("No pad_coord"))?;
                     Ok(AirFacility::AirshipTerminal {
                         code: (*code).into(),
                         pad_coord: coords_to_vec(pad_coord)?,
+                        name: None,
                     })
                 }
                 Some(ty) => {
@@ -41,7 +63,7 @@ pub fn get_air_facilities(str: &str) -> Result<Vec<AirFacility>> {
                         "Airfield" => PlaneFacilityType::Airfield,
                         _ => return Err(eyre!("Invalid type `{ty}`")),
                     };
-                    let mut i = 3;
+                    let mut i = COORDS_COLUMN;
                     let mut runways = smallvec![];
                     while if let Some(cell) = row.get(i) {
                         !cell.is_empty()
@@ -78,10 +100,26 @@ pub fn get_air_facilities(str: &str) -> Result<Vec<AirFacility>> {
                             length,
                         });
                     }
+                    let reference_point = match reference_point_column
+                        .and_then(|i| row.get(i))
+                        .filter(|cell| !cell.trim().is_empty())
+                    {
+                        Some(cell) => coords_to_vec(cell)
+                            .map_err(|err| {
+                                warn!(
+                                    code,
+                                    "Invalid reference point `{cell}`, using the runways: {err}"
+                                );
+                            })
+                            .ok(),
+                        None => None,
+                    };
                     Ok(AirFacility::Airport {
                         code: (*code).into(),
                         ty,
                         runways,
+                        name: None,
+                        reference_point,
                     })
                 }
                 None => Err(eyre!("No type")),
@@ -89,3 +127,47 @@ pub fn get_air_facilities(str: &str) -> Result<Vec<AirFacility>> {
         })
         .collect::<Result<Vec<_>>>()
 }
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::data_types::airport::AirFacility;
+    use glam::vec2;
+
+    use crate::get_air_facilities::get_air_facilities;
+
+    #[test]
+    fn air_facilities_reference_points() -> Result<()> {
+        let air_facilities = get_air_facilities(
+            "Code,Type,Reference point,Runways
+AAA,Airport,10 -20,0 0,100 0,09 - 27,Large
+BBB,Airport,north,0 0,100 0,09 - 27,Large
+CCC,Airfield,,0 0,100 0,09 - 27,Small
+HEL,Heliport,,5 5",
+        )?;
+        let reference_points = air_facilities
+            .iter()
+            .map(|af| match af {
+                AirFacility::Airport {
+                    reference_point, ..
+                } => *reference_point,
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        // the y axis is flipped
+        assert_eq!(reference_points, [Some(vec2(10.0, 20.0)), None, None, None]);
+        assert_eq!(air_facilities[1].main_coord(), Some(vec2(50.0, 0.0)));
+        assert_eq!(air_facilities[3].main_coord(), Some(vec2(5.0, -5.0)));
+        Ok(())
+    }
+
+    #[test]
+    fn air_facilities_no_reference_point_column() -> Result<()> {
+        let air_facilities = get_air_facilities(
+            "Code,Type,Notes,Runways
+AAA,Airport,10 -20,0 0,100 0,09 - 27,Large",
+        )?;
+        assert_eq!(air_facilities[0].main_coord(), Some(vec2(50.0, 0.0)));
+        Ok(())
+    }
+}
@@ -137,31 +137,87 @@
     clippy::wildcard_dependencies
 )]
 
+mod airport_names;
+mod airway_overrides;
 mod generate_airways;
+mod geojson;
 mod get_air_facilities;
 mod get_waypoints;
 
-use std::io::Read;
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
 
+use clap::{Parser, Subcommand};
 use color_eyre::eyre::Result;
-use common::data_types::RawData;
-use tracing::info;
+use common::data_types::{airport_names::AirportNames, RawData};
+use smol_str::SmolStr;
+use tracing::{info, warn};
 use tracing_subscriber::EnvFilter;
 
 use crate::{
-    generate_airways::generate_airways, get_air_facilities::get_air_facilities,
+    airport_names::merge_airport_names,
+    airway_overrides::{apply_airway_overrides, get_airway_overrides, AirwayOverrides},
+    generate_airways::generate_airways,
+    geojson::to_geojson,
+    get_air_facilities::get_air_facilities,
     get_waypoints::get_waypoints,
 };
 
 const AIR_FACILITY_LIST_URL: &str = "https://docs.google.com/spreadsheets/d/11E60uIBKs5cOSIRHLz0O0nLCefpj7HgndS1gIXY_1hw/export?format=csv";
 const WAYPOINT_LIST_URL: &str = "https://docs.google.com/spreadsheets/d/11E60uIBKs5cOSIRHLz0O0nLCefpj7HgndS1gIXY_1hw/export?format=csv&gid=707730663";
 
-fn main() -> Result<()> {
-    tracing_subscriber::fmt()
-        .event_format(tracing_subscriber::fmt::format().without_time().compact())
-        .with_env_filter(EnvFilter::from_env("RUST_LOG"))
-        .init();
+/// Generates the navigation dataset from the MRT air facility and waypoint sheets
+#[derive(Parser, Debug)]
+#[command(version)]
+struct Args {
+    /// Directory that outputs are written to, unless given their own path with `--out`
+    #[arg(long, default_value = "data")]
+    out_dir: PathBuf,
+    /// Hand-curated airway overrides, skipped if the file doesn't exist
+    #[arg(long, default_value = "data/airway_overrides.txt")]
+    airway_overrides: PathBuf,
+    /// Display names of air facilities, skipped if the file doesn't exist
+    #[arg(long, default_value = "data/airport_names.txt")]
+    airport_names: PathBuf,
+    /// Output to generate. Every output is generated if omitted
+    #[command(subcommand)]
+    command: Option<Command>,
+}
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Generate every output
+    All {
+        /// Also generate the `GeoJSON` export
+        #[arg(long)]
+        geojson: bool,
+    },
+    /// Generate the msgpack dataset embedded by the server and the editor
+    RawData {
+        /// Output path [default: `<OUT_DIR>/raw_data`]
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
+    /// Generate the JSON list of airway coordinate pairs
+    AirwayCoords {
+        /// Output path [default: `<OUT_DIR>/airway_coords.json`]
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
+    /// Generate a `GeoJSON` export of waypoints, airways, runways and air facilities
+    Geojson {
+        /// Output path [default: `<OUT_DIR>/navdata.geojson`]
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
+}
+
+/// Returns the dataset along with the sources it was generated from
+fn get_raw_data(airway_overrides: &Path, airport_names: &Path) -> Result<(RawData, Vec<SmolStr>)> {
+    let mut sources = vec![AIR_FACILITY_LIST_URL.into(), WAYPOINT_LIST_URL.into()];
     let air_facilities = {
         let mut str = String::new();
         reqwest::blocking::get(AIR_FACILITY_LIST_URL)?.read_to_string(&mut str)?;
@@ -174,43 +230,105 @@ fn main() -> Result<()> {
         info!("Waypoints retrieved");
         str
     };
-    let air_facilities = get_air_facilities(&air_facilities)?;
+    let airway_overrides = if airway_overrides.exists() {
+        let overrides = get_airway_overrides(&fs::read_to_string(airway_overrides)?)?;
+        info!("Airway overrides retrieved");
+        sources.push(airway_overrides.to_string_lossy().into());
+        overrides
+    } else {
+        info!("No airway overrides found");
+        AirwayOverrides::default()
+    };
+    let mut air_facilities = get_air_facilities(&air_facilities)?;
+    if airport_names.exists() {
+        let names = AirportNames::from_string(&fs::read_to_string(airport_names)?)?;
+        let report = merge_airport_names(&mut air_facilities, &names);
+        for line in &report {
+            warn!("{line}");
+        }
+        info!(unmatched = report.len(), "Airport names merged");
+        sources.push(airport_names.to_string_lossy().into());
+    } else {
+        info!("No airport names found");
+    }
     let waypoints = get_waypoints(&waypoints)?;
-    let airways = generate_airways(&waypoints);
-    let airway_coords = airways
+    let mut airways = generate_airways(&waypoints);
+    apply_airway_overrides(&mut airways, &airway_overrides, &waypoints)?;
+    Ok((
+        RawData {
+            air_facilities,
+            waypoints,
+            airways,
+        },
+        sources,
+    ))
+}
+
+fn save_raw_data(raw_data: &RawData, sources: Vec<SmolStr>, path: &Path) -> Result<()> {
+    fs::write(path, raw_data.to_vec(sources)?)?;
+    info!(?path, "Saved raw_data");
+    Ok(())
+}
+
+fn save_airway_coords(raw_data: &RawData, path: &Path) -> Result<()> {
+    let airway_coords = raw_data
+        .airways
         .iter()
         .filter_map(|aw| {
             Some((
-                waypoints
+                raw_data
+                    .waypoints
                     .iter()
                     .find(|w| w.name == aw.waypoint1)?
-                    .coords
-                    .to_owned(),
-                waypoints
+                    .coords,
+                raw_data
+                    .waypoints
                     .iter()
                     .find(|w| w.name == aw.waypoint2)?
-                    .coords
-                    .to_owned(),
+                    .coords,
             ))
         })
         .collect::<Vec<_>>();
-    let raw_data = RawData {
-        air_facilities,
-        waypoints,
-        airways,
-    };
+    fs::write(path, serde_json::to_string(&airway_coords)?)?;
+    info!(?path, "Saved airway coords");
+    Ok(())
+}
+
+fn save_geojson(raw_data: &RawData, path: &Path) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(&to_geojson(raw_data))?)?;
+    info!(?path, "Saved GeoJSON");
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .event_format(tracing_subscriber::fmt::format().without_time().compact())
+        .with_env_filter(EnvFilter::from_env("RUST_LOG"))
+        .init();
+    let args = Args::parse();
 
-    let path = std::env::args()
-        .nth(1)
-        .unwrap_or_else(|| "data/raw_data".into());
-    std::fs::write(path, rmp_serde::to_vec(&raw_data)?)?;
-    info!("Saved raw_data");
+    let (raw_data, sources) = get_raw_data(&args.airway_overrides, &args.airport_names)?;
+    let out =
+        |path: Option<PathBuf>, default: &str| path.unwrap_or_else(|| args.out_dir.join(default));
 
-    let path2 = std::env::args()
-        .nth(1)
-        .unwrap_or_else(|| "data/airway_coords.json".into());
-    std::fs::write(path2, serde_json::to_string(&airway_coords)?)?;
-    info!("Saved airway_coords.json");
+    match args.command.unwrap_or(Command::All { geojson: false }) {
+        Command::All { geojson } => {
+            save_raw_data(&raw_data, sources, &out(None, "raw_data"))?;
+            save_airway_coords(&raw_data, &out(None, "airway_coords.json"))?;
+            if geojson {
+                save_geojson(&raw_data, &out(None, "navdata.geojson"))?;
+            }
+        }
+        Command::RawData { out: path } => {
+            save_raw_data(&raw_data, sources, &out(path, "raw_data"))?;
+        }
+        Command::AirwayCoords { out: path } => {
+            save_airway_coords(&raw_data, &out(path, "airway_coords.json"))?;
+        }
+        Command::Geojson { out: path } => {
+            save_geojson(&raw_data, &out(path, "navdata.geojson"))?;
+        }
+    }
 
     Ok(())
 }
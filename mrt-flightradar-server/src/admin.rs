@@ -0,0 +1,52 @@
+use common::data_types::{raw_data_path, RawData, RAW_DATA, RAW_DATA_PATH_VAR};
+use rocket::{
+    http::Status,
+    request::{FromRequest, Outcome},
+    Request,
+};
+use tracing::{info, warn};
+
+/// Environment variable holding the token that admin requests must send in `X-Admin-Token`.
+/// Admin endpoints are disabled if it is unset
+pub const ADMIN_TOKEN_VAR: &str = "MRT_FLIGHTRADAR_ADMIN_TOKEN";
+
+pub struct Admin;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Admin {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Ok(token) = std::env::var(ADMIN_TOKEN_VAR) else {
+            return Outcome::Error((Status::NotFound, ()));
+        };
+        if req.headers().get_one("X-Admin-Token") == Some(&*token) {
+            Outcome::Success(Self)
+        } else {
+            warn!("Admin request with invalid token");
+            Outcome::Error((Status::Unauthorized, ()))
+        }
+    }
+}
+
+/// Swaps in the dataset at the configured path. Flights in progress keep their routes
+#[rocket::post("/admin/reload")]
+pub fn reload(_admin: Admin) -> Result<String, (Status, String)> {
+    let path = raw_data_path().ok_or_else(|| {
+        (
+            Status::Conflict,
+            format!("`{RAW_DATA_PATH_VAR}` is not set"),
+        )
+    })?;
+    let raw_data =
+        RawData::from_file(&path).map_err(|err| (Status::UnprocessableEntity, err.to_string()))?;
+    let msg = format!(
+        "Loaded {} air facilities, {} waypoints and {} airways",
+        raw_data.air_facilities.len(),
+        raw_data.waypoints.len(),
+        raw_data.airways.len()
+    );
+    RAW_DATA.set(raw_data);
+    info!(?path, "{msg}");
+    Ok(msg)
+}
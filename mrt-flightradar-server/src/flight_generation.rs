@@ -2,10 +2,13 @@ use std::{sync::Arc, time::SystemTime};
 
 use color_eyre::eyre::{eyre, Result};
 use common::{
-    data_types::{airport::AirFacility, RAW_DATA},
+    data_types::{airport::AirFacility, fleet::aircraft_types, RAW_DATA},
     flight_route::get_flight_route,
 };
-use rand::{prelude::SliceRandom, Rng};
+use rand::{
+    prelude::{IteratorRandom, SliceRandom},
+    Rng,
+};
 use tokio::time::Duration;
 use tracing::{debug, info};
 
@@ -20,19 +23,21 @@ const AIRLINE_NAMES: [&str; 6] = [
     "Lumeva Airlink",
 ];
 
-const AIRCRAFT_NAMES: [&str; 4] = ["Stratus SA-1", "IntraJet ExpiXS", "Fighter Jet", "Dragon"];
-
 #[tracing::instrument]
 pub async fn generate_flights() -> Result<Vec<Arc<ActiveFlight<'static>>>> {
     let mut new_flights = vec![];
     let num_new_flights = rand::thread_rng().gen_range(0..5);
     info!("Generating {num_new_flights} new flights");
+    let raw_data = RAW_DATA.get();
     for _ in 0..num_new_flights {
-        let airports = RAW_DATA
+        let airports = raw_data
             .air_facilities
             .iter()
             .filter_map(|af| {
-                if let AirFacility::Airport { code, ty, runways } = &af {
+                if let AirFacility::Airport {
+                    code, ty, runways, ..
+                } = &af
+                {
                     Some((code, ty, runways))
                 } else {
                     None
@@ -54,7 +59,7 @@ pub async fn generate_flights() -> Result<Vec<Arc<ActiveFlight<'static>>>> {
                 .choose(&mut rand::thread_rng())
                 .ok_or_else(|| eyre!("No runways"))?,
         );
-        let (waypoints, route) = get_flight_route(runway1, runway2)?;
+        let (waypoints, route) = get_flight_route(&raw_data, runway1, runway2)?;
         let depart_time = SystemTime::now() + Duration::from_secs(30);
         let arrival_time = depart_time + Duration::from_secs(route.time_taken() as u64);
         new_flights.push(Arc::new(ActiveFlight {
@@ -64,11 +69,18 @@ pub async fn generate_flights() -> Result<Vec<Arc<ActiveFlight<'static>>>> {
             arrival_time,
             info: ActiveFlightInfo {
                 airline_name: AIRLINE_NAMES.choose(&mut rand::thread_rng()).unwrap(),
-                aircraft: AIRCRAFT_NAMES.choose(&mut rand::thread_rng()).unwrap(),
+                aircraft: aircraft_types()
+                    .choose(&mut rand::thread_rng())
+                    .ok_or_else(|| eyre!("No aircraft types"))?,
                 registry_code: "".into(),
-                from: airport1,
-                to: airport2,
-                waypoints,
+                from: (*airport1).to_owned(),
+                to: (*airport2).to_owned(),
+                airways: raw_data
+                    .airway_names(&waypoints)
+                    .into_iter()
+                    .map(Option::<&_>::cloned)
+                    .collect(),
+                waypoints: waypoints.into_iter().cloned().collect(),
             },
         }));
     }
@@ -137,16 +137,25 @@
     clippy::wildcard_dependencies
 )]
 
+mod admin;
 mod flight_generation;
 mod purge;
 mod status_calculation;
 mod types_consts;
 
-use std::{collections::HashMap, time::UNIX_EPOCH};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::UNIX_EPOCH,
+};
 
 use color_eyre::eyre::Result;
 use common::{
-    data_types::{vec::Pos, RAW_DATA},
+    data_types::{
+        airport_names::{AirportNames, AIRPORT_NAMES},
+        timetable::AirportCode,
+        vec::Pos,
+        RAW_DATA,
+    },
     flight_route::types::path::Path,
 };
 use glam::Vec2;
@@ -158,6 +167,7 @@ use rocket::{
     routes, Request, Response,
 };
 use serde::Serialize;
+use smol_str::SmolStr;
 use tokio::time::Duration;
 use tracing::error;
 use tracing_subscriber::EnvFilter;
@@ -213,18 +223,60 @@ async fn flights() -> CustomMsgPack<Vec<ActiveFlight<'static>>> {
 }
 
 #[rocket::get("/airports")]
-fn airports() -> CustomMsgPack<HashMap<&'static str, Pos<Vec2>>> {
+fn airports() -> CustomMsgPack<HashMap<SmolStr, Pos<Vec2>>> {
     CustomMsgPack(
         RAW_DATA
+            .get()
             .waypoints
             .iter()
             .filter_map(|w| {
-                (w.name.starts_with("AA") && &*w.name != "RDV").then(|| (&w.name[2..], w.coords))
+                (w.name.starts_with("AA") && &*w.name != "RDV")
+                    .then(|| (w.name[2..].into(), w.coords))
             })
             .collect::<HashMap<_, _>>(),
     )
 }
 
+/// Reference point of every air facility, which distances to it are measured from
+#[rocket::get("/reference_points")]
+fn reference_points() -> CustomMsgPack<HashMap<AirportCode, Pos<Vec2>>> {
+    CustomMsgPack(
+        RAW_DATA
+            .get()
+            .air_facilities
+            .iter()
+            .filter_map(|af| Some((af.code().to_owned(), af.main_coord()?)))
+            .collect(),
+    )
+}
+
+/// Names from the raw data, falling back to `airport_names.txt` for air facilities without one
+fn merged_airport_names() -> AirportNames {
+    AIRPORT_NAMES.merged(&RAW_DATA.get().air_facilities)
+}
+
+#[rocket::get("/airport_names")]
+fn airport_names() -> CustomMsgPack<BTreeMap<AirportCode, SmolStr>> {
+    CustomMsgPack(
+        merged_airport_names()
+            .with_prefix("")
+            .map(|(code, name)| (code.to_owned(), name.to_owned()))
+            .collect(),
+    )
+}
+
+/// Airports whose code or name matches `q`, best match first
+#[rocket::get("/airport_names/search?<q>")]
+fn search_airport_names(q: &str) -> CustomMsgPack<Vec<(AirportCode, SmolStr)>> {
+    CustomMsgPack(
+        merged_airport_names()
+            .search(q)
+            .into_iter()
+            .map(|(code, name)| (code.to_owned(), name.to_owned()))
+            .collect(),
+    )
+}
+
 #[rocket::get("/route/<id>")]
 async fn flight_route(id: String) -> Option<CustomMsgPack<Vec<Pos<Vec2>>>> {
     let id = id.parse::<Uuid>().ok()?;
@@ -285,7 +337,19 @@ async fn main() -> Result<()> {
         .init();
 
     let r = rocket::build()
-        .mount("/", routes![actions, flights, flight_route, airports])
+        .mount(
+            "/",
+            routes![
+                actions,
+                flights,
+                flight_route,
+                airports,
+                reference_points,
+                airport_names,
+                search_airport_names,
+                admin::reload
+            ],
+        )
         .attach(CORS)
         .ignite()
         .await?;
@@ -19,9 +19,10 @@ pub struct ActiveFlightInfo<'a> {
     pub airline_name: &'a str,
     pub aircraft: &'a str,
     pub registry_code: SmolStr,
-    pub from: &'a AirportCode,
-    pub to: &'a AirportCode,
-    pub waypoints: Vec<&'static Waypoint>,
+    pub from: AirportCode,
+    pub to: AirportCode,
+    pub waypoints: Vec<Waypoint>,
+    pub airways: Vec<Option<SmolStr>>,
 }
 
 fn serialise_as_timestamp<S: Serializer>(a: &SystemTime, ser: S) -> Result<S::Ok, S::Error> {
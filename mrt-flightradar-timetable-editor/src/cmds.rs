@@ -1,30 +1,66 @@
-use std::{iter::Peekable, str::Split};
+#[cfg(test)]
+use std::{
+    env, fs,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
+};
+use std::{
+    iter::Peekable,
+    path::{Path, PathBuf},
+    str::{FromStr, Split},
+};
 
+use chrono::NaiveDate;
 use color_eyre::eyre::{eyre, Result};
 #[cfg(test)]
-use common::data_types::timetable::AirlineTimetable;
-#[cfg(test)]
-use common::data_types::RAW_DATA;
+use common::data_types::{RawData, RAW_DATA};
 use glam::Vec2;
-use itertools::Itertools;
+use itertools::{EitherOrBoth, Itertools};
 use smol_str::SmolStr;
 
-use crate::cmds::e::estimate_time;
+use crate::{
+    cmds::e::estimate_time,
+    filter::Filter,
+    save::{is_saved, save},
+};
 
+pub mod ba;
+pub mod bap;
+pub mod bf;
+pub mod bs;
 pub mod c;
 pub mod d;
 pub mod e;
+pub mod export;
+pub mod f;
+pub mod fc;
 pub mod h;
 pub mod i;
 pub mod ie;
+pub mod import;
 pub mod is;
 pub mod m;
 pub mod n;
+pub mod nc;
 pub mod q;
+pub mod r;
+pub mod rc;
+pub mod re;
 pub mod sa;
 pub mod sae;
 pub mod sas;
 pub mod sd;
+pub mod st;
+pub mod u;
+pub mod w;
+pub mod wa;
+pub mod wc;
+pub mod wcp;
+pub mod wm;
+pub mod ws;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Action {
@@ -32,6 +68,229 @@ pub enum Action {
     Hold,
     Msg(String),
     Quit(String),
+    /// Preview of a change, and the flights to replace the timetable's with if it is confirmed
+    Confirm(String, Vec<Flight>),
+}
+
+/// Number of changes that can be undone
+const UNDO_LIMIT: usize = 100;
+
+/// Editor state besides the timetable that lasts between commands
+#[derive(Clone, Debug, Default)]
+pub struct State {
+    pub filter: Filter,
+    /// Flights before each change, most recent last
+    undo: Vec<Vec<Flight>>,
+    /// File the timetable being edited was opened from
+    pub path: PathBuf,
+    /// Other airlines open alongside the one being edited and their files, sorted by name
+    pub workspace: Vec<(AirlineTimetable, PathBuf)>,
+    /// Only save on `w`, instead of after every command
+    pub manual_save: bool,
+    /// Aircraft of the airline being edited, from its fleet file
+    pub fleet: Fleet,
+}
+
+impl State {
+    fn record(&mut self, before: Vec<Flight>, file: &AirlineTimetable) {
+        if before != file.flights {
+            self.undo.push(before);
+            if self.undo.len() > UNDO_LIMIT {
+                self.undo.remove(0);
+            }
+        }
+    }
+
+    /// Replaces the flights with a confirmed change, as one undo step
+    pub fn apply(&mut self, file: &mut AirlineTimetable, flights: Vec<Flight>) {
+        let before = std::mem::replace(&mut file.flights, flights);
+        self.record(before, file);
+    }
+
+    /// Reads the fleet file of the airline being edited, if it has one
+    pub fn load_fleet(&mut self) -> Result<()> {
+        self.fleet = Fleet::from_timetable_path(&self.path)?;
+        Ok(())
+    }
+
+    /// The timetable and every other airline in the workspace, with the files they are saved to
+    fn airlines<'a>(
+        &'a self,
+        file: &'a AirlineTimetable,
+    ) -> impl Iterator<Item = (&'a AirlineTimetable, &'a Path)> {
+        std::iter::once((file, self.path.as_path())).chain(
+            self.workspace
+                .iter()
+                .map(|(airline, path)| (airline, path.as_path())),
+        )
+    }
+
+    /// Saves the timetable and every other airline in the workspace that changed
+    pub fn save(&self, file: &AirlineTimetable) -> Result<()> {
+        for (airline, path) in self.airlines(file) {
+            save(airline, path)?;
+        }
+        Ok(())
+    }
+
+    /// Saves after a command, unless autosave is off
+    pub fn autosave(&self, file: &AirlineTimetable) -> Result<()> {
+        if self.manual_save {
+            Ok(())
+        } else {
+            self.save(file)
+        }
+    }
+
+    /// Whether any airline in the workspace has changes that aren't saved
+    pub fn unsaved(&self, file: &AirlineTimetable) -> bool {
+        self.airlines(file)
+            .any(|(airline, path)| !is_saved(airline, path))
+    }
+}
+
+pub fn run(
+    cmd_str: &str,
+    file: &mut AirlineTimetable,
+    state: &mut State,
+    air_facilities: &[AirFacility],
+) -> Result<Action> {
+    let cmd_str = &mut cmd_str.split(' ').peekable();
+    let cmd = cmd_str.next();
+    // these don't change the timetable, or change other airlines too, so aren't recorded for undo
+    match cmd {
+        Some("q") => return q::q(cmd_str, file, state),
+        Some("u") => return u::u(file, &mut state.undo),
+        Some("ws") => return ws::ws(cmd_str, file, state),
+        Some("wm") => return wm::wm(cmd_str, file, state),
+        Some("wcp") => return wcp::wcp(cmd_str, file, state),
+        Some("wc") => return wc::wc(file, &state.workspace),
+        Some("w") => return w::w(file, state),
+        Some("wa") => return wa::wa(cmd_str, state),
+        _ => {}
+    }
+    let before = file.flights.to_owned();
+    let filter = &mut state.filter;
+    let action = match cmd {
+        Some("h") => h::h(),
+        Some("i") => i::i(cmd_str, file, air_facilities, &state.fleet),
+        Some("is") => is::is(cmd_str, file, air_facilities, &state.fleet),
+        Some("ie") => ie::ie(cmd_str, file, air_facilities, &state.fleet),
+        Some("c") => c::c(cmd_str, file),
+        Some("d") => d::d(cmd_str, file),
+        Some("m") => m::m(cmd_str, file),
+        Some("e") => e::e(cmd_str, air_facilities),
+        Some("import") => import::import(cmd_str, file, &state.path),
+        Some("export") => export::export(cmd_str, file, &state.path),
+        Some("n") => n::n(cmd_str),
+        Some("nc") => nc::nc(air_facilities),
+        Some("f") => f::f(cmd_str, filter),
+        Some("fc") => fc::fc(filter),
+        Some("bs") => bs::bs(cmd_str, file, filter),
+        Some("ba") => ba::ba(cmd_str, file, filter),
+        Some("bap") => bap::bap(cmd_str, file, filter),
+        Some("bf") => bf::bf(cmd_str, file, filter),
+        Some("r") => r::r(cmd_str, file),
+        Some("re") => re::re(cmd_str, file, air_facilities),
+        Some("rc") => rc::rc(cmd_str, file),
+        Some("sa") => sa::sa(cmd_str, file, air_facilities),
+        Some("sae") => sae::sae(cmd_str, file, air_facilities),
+        Some("sas") => sas::sas(cmd_str, file, air_facilities),
+        Some("sd") => sd::sd(cmd_str, file),
+        Some("st") => st::st(cmd_str, file, &state.path, air_facilities),
+        Some(a) => Err(eyre!("Unknown command `{a}`")),
+        None => Ok(Action::Refresh),
+    };
+    // undo any command that leaves a flight that would be saved but couldn't be read back
+    if let Err(err) = AirlineTimetable::validate_flights(&file.flights) {
+        file.flights = before;
+        return Err(err);
+    }
+    state.record(before, file);
+    action
+}
+
+fn flight_row(index: usize, flight: &Flight) -> String {
+    format!(
+        "{index}\t\"{}\"\t{}\t{}\t{}",
+        flight.aircraft,
+        flight.registry,
+        schedule(flight),
+        flight.segments.iter().join(";")
+    )
+}
+
+/// Formats a number of minutes like `1h05`, or `-1h05` if negative
+pub fn duration(minutes: i32) -> String {
+    format!(
+        "{}{}h{:02}",
+        if minutes < 0 { "-" } else { "" },
+        (minutes / 60).abs(),
+        (minutes % 60).abs()
+    )
+}
+
+/// When a flight operates, eg. `12345.. 2026-06-01..2026-08-31`, leaving out dates that aren't set
+pub fn schedule(flight: &Flight) -> String {
+    let date = |date: Option<NaiveDate>| {
+        date.map(|date| date.format(DATE_FORMAT).to_string())
+            .unwrap_or_default()
+    };
+    if flight.valid_from.is_none() && flight.valid_to.is_none() {
+        flight.days.to_string()
+    } else {
+        format!(
+            "{} {}..{}",
+            flight.days,
+            date(flight.valid_from),
+            date(flight.valid_to)
+        )
+    }
+}
+
+/// Asks for confirmation to replace the timetable's flights with `new`, previewing the rows that change
+fn confirm(old: &[Flight], new: Vec<Flight>) -> Result<Action> {
+    let mut changed = 0;
+    let preview = old
+        .iter()
+        .zip_longest(&new)
+        .enumerate()
+        .filter_map(|(i, pair)| {
+            let rows = match pair {
+                EitherOrBoth::Both(old, new) if old == new => return None,
+                EitherOrBoth::Both(old, new) => {
+                    format!("- {}\n+ {}", flight_row(i, old), flight_row(i, new))
+                }
+                EitherOrBoth::Left(old) => format!("- {}", flight_row(i, old)),
+                EitherOrBoth::Right(new) => format!("+ {}", flight_row(i, new)),
+            };
+            changed += 1;
+            Some(rows)
+        })
+        .join("\n");
+    if changed == 0 {
+        return Err(eyre!("No flights would change"));
+    }
+    AirlineTimetable::validate_flights(&new)?;
+    Ok(Action::Confirm(
+        format!("{changed} flight(s) will change:\n{preview}"),
+        new,
+    ))
+}
+
+/// Applies `f` to a copy of every flight shown by the filter, then asks for confirmation
+fn bulk(
+    file: &AirlineTimetable,
+    filter: &Filter,
+    mut f: impl FnMut(&mut Flight) -> Result<()>,
+) -> Result<Action> {
+    let mut flights = file.flights.to_owned();
+    for flight in &mut flights {
+        if filter.matches(flight) {
+            f(flight)?;
+        }
+    }
+    confirm(&file.flights, flights)
 }
 
 fn get_index(
@@ -66,6 +325,27 @@ fn get_time(cmd_str: &mut Peekable<Split<char>>, name: &str) -> Result<Time> {
     }
 }
 
+fn get_date(cmd_str: &mut Peekable<Split<char>>, name: &str) -> Result<NaiveDate> {
+    if let Some(date) = cmd_str.next() {
+        NaiveDate::parse_from_str(date, DATE_FORMAT)
+            .ok()
+            .ok_or_else(|| eyre!("Invalid date `{date}`, expected YYYY-MM-DD"))
+    } else {
+        Err(eyre!("Missing argument <{name}>"))
+    }
+}
+
+fn get_number<T: FromStr>(cmd_str: &mut Peekable<Split<char>>, name: &str) -> Result<T> {
+    if let Some(number) = cmd_str.next() {
+        number
+            .parse()
+            .ok()
+            .ok_or_else(|| eyre!("Invalid number `{number}`"))
+    } else {
+        Err(eyre!("Missing argument <{name}>"))
+    }
+}
+
 fn get_aircraft(cmd_str: &mut Peekable<Split<char>>, _: &str) -> Result<SmolStr> {
     if let Some(next) = cmd_str.peek() {
         if !next.starts_with('"') {
@@ -113,13 +393,11 @@ fn get_flight_segment(
     let flight_no = arg!(cmd_str "f" get_str)?;
     let airport = arg!(cmd_str "a" get_airport)?;
     let depart_time = if let Some(prev_seg) = prev_seg {
-        let f = || {
-            Ok(prev_seg.depart_time
-                + estimate_time(
-                    *get_main_coord(&prev_seg.airport, air_facilities)?,
-                    *get_main_coord(&airport, air_facilities)?,
-                ))
-        };
+        let f =
+            || {
+                Ok(prev_seg.depart_time
+                    + estimate_time(&prev_seg.airport, &airport, air_facilities)?.0)
+            };
         if let Some(a) = cmd_str.peek() {
             if a.trim() == "_" {
                 cmd_str.next();
@@ -138,15 +416,28 @@ fn get_flight_segment(
         flight_no,
         depart_time,
         airport,
+        arrive_time: None,
     })
 }
 
+/// Parses a flight. The aircraft may be left out if the registry is in the fleet
 fn get_flight(
     cmd_str: &mut Peekable<Split<char>>,
     air_facilities: &[AirFacility],
+    fleet: &Fleet,
 ) -> Result<Flight> {
-    let aircraft = arg!(cmd_str "aircraft" get_aircraft)?;
-    let reg = arg!(cmd_str "reg" get_str)?;
+    let (aircraft, reg) = if cmd_str.peek().is_some_and(|next| !next.starts_with('"')) {
+        let reg = arg!(cmd_str "reg" get_str)?;
+        let aircraft = fleet.aircraft(&reg).ok_or_else(|| {
+            eyre!("Registry {reg} is not in the fleet, so \"<aircraft>\" must be given")
+        })?;
+        (aircraft.to_owned(), reg)
+    } else {
+        (
+            arg!(cmd_str "aircraft" get_aircraft)?,
+            arg!(cmd_str "reg" get_str)?,
+        )
+    };
     let mut segments = vec![];
     while cmd_str.peek().is_some() {
         segments.push(get_flight_segment(
@@ -159,22 +450,53 @@ fn get_flight(
         aircraft,
         registry: reg,
         segments,
+        comments: vec![],
+        days: Days::ALL,
+        valid_from: None,
+        valid_to: None,
     })
 }
 
-pub fn get_main_coord<'a>(
+/// Index of the airline in the workspace named by the rest of the command, case-insensitively
+fn get_airline(
+    cmd_str: &mut Peekable<Split<char>>,
+    workspace: &[(AirlineTimetable, PathBuf)],
+) -> Result<usize> {
+    let name = cmd_str.join(" ");
+    if name.is_empty() {
+        return Err(eyre!("Missing argument <airline>"));
+    }
+    workspace
+        .iter()
+        .position(|(airline, _)| airline.name.eq_ignore_ascii_case(&name))
+        .ok_or_else(|| eyre!("No other airline named `{name}` in the workspace"))
+}
+
+/// Takes the rest of the command as a path, relative to the directory of the timetable
+fn get_path(cmd_str: &mut Peekable<Split<char>>, file_path: &Path) -> Result<PathBuf> {
+    let path = cmd_str.join(" ");
+    if path.is_empty() {
+        return Err(eyre!("Missing argument <path>"));
+    }
+    Ok(file_path
+        .parent()
+        .map_or_else(|| PathBuf::from(&path), |dir| dir.join(&path)))
+}
+
+pub fn get_air_facility<'a>(
     airport: &SmolStr,
     air_facilities: &'a [AirFacility],
-) -> Result<&'a Pos<Vec2>> {
-    if let Some(o) = air_facilities.iter().find(|a| *a.code() == *airport) {
-        if let Some(c) = o.main_coord() {
-            Ok(c)
-        } else {
-            Err(eyre!("Airport `{airport}` has no main coords"))
-        }
-    } else {
-        Err(eyre!("Invalid airport code `{airport}`"))
-    }
+) -> Result<&'a AirFacility> {
+    air_facilities
+        .iter()
+        .find(|a| *a.code() == *airport)
+        .ok_or_else(|| eyre!("Invalid airport code `{airport}`"))
+}
+
+pub fn get_main_coord(airport: &SmolStr, air_facilities: &[AirFacility]) -> Result<Pos<Vec2>> {
+    get_air_facility(airport, air_facilities)?
+        .main_coord()
+        .ok_or_else(|| eyre!("Airport `{airport}` has no main coords"))
 }
 
 #[macro_export]
@@ -182,8 +504,8 @@ macro_rules! arg {
     ($cmd_str:ident $name:literal get_index, $file:ident, $opr:ident) => {
         $crate::cmds::get_index($cmd_str, |index| index.$opr(&$file.flights.len()), $name)
     };
-    ($cmd_str:ident $name:literal get_flight, $air_facilities:expr) => {
-        $crate::cmds::get_flight($cmd_str, $air_facilities)
+    ($cmd_str:ident $name:literal get_flight, $air_facilities:expr, $fleet:expr) => {
+        $crate::cmds::get_flight($cmd_str, $air_facilities, $fleet)
     };
     ($cmd_str:ident $name:literal get_flight_segment, $air_facilities:expr, $prev_seg:expr) => {
         $crate::cmds::get_flight_segment($cmd_str, $air_facilities, $prev_seg)
@@ -217,8 +539,9 @@ macro_rules! to_cmd_str {
 }
 
 #[cfg(test)]
-fn test_setup() -> Result<(&'static Vec<AirFacility>, AirlineTimetable)> {
-    let air_facilities = &RAW_DATA.air_facilities;
+pub fn test_setup() -> Result<(&'static Vec<AirFacility>, AirlineTimetable)> {
+    static SNAPSHOT: OnceLock<Arc<RawData>> = OnceLock::new();
+    let air_facilities = &SNAPSHOT.get_or_init(|| RAW_DATA.get()).air_facilities;
     let file = AirlineTimetable::from_string(
         include_str!("../../data/test-timetable.fpln"),
         "Test".into(),
@@ -226,19 +549,55 @@ fn test_setup() -> Result<(&'static Vec<AirFacility>, AirlineTimetable)> {
     Ok((air_facilities, file))
 }
 
+/// Empty temporary directory for a test to write files in, deleted again when dropped
+#[cfg(test)]
+pub struct TestDir(PathBuf);
+#[cfg(test)]
+impl TestDir {
+    pub fn new() -> Result<Self> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let dir = env::temp_dir().join(format!(
+            "mrt-fr-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        fs::create_dir_all(&dir)?;
+        Ok(Self(dir))
+    }
+}
+#[cfg(test)]
+impl Deref for TestDir {
+    type Target = Path;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[cfg(test)]
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
 use arg;
 use common::data_types::{
     airport::AirFacility,
+    fleet::Fleet,
     time::Time,
-    timetable::{AirportCode, Flight, FlightSegment},
+    timetable::{AirlineTimetable, AirportCode, Days, Flight, FlightSegment, DATE_FORMAT},
     vec::Pos,
 };
 
 #[cfg(test)]
 mod tests {
     use color_eyre::eyre::Result;
+    use common::data_types::fleet::Fleet;
+    use itertools::Itertools;
 
-    use crate::cmds::{get_aircraft, get_airport, get_flight, test_setup};
+    use crate::cmds::{duration, get_aircraft, get_airport, get_flight, test_setup};
 
     #[test]
     pub fn get_aircraft_normal() {
@@ -268,7 +627,7 @@ mod tests {
 
         let mut cmd_str = to_cmd_str!(r#""Test Aircraft" REG AB1234 PRA 0000"#);
         assert!(
-            get_flight(&mut cmd_str, air_facilities).is_ok(),
+            get_flight(&mut cmd_str, air_facilities, &Fleet::default()).is_ok(),
             "Unsuccessful flight parsing"
         );
         Ok(())
@@ -280,7 +639,7 @@ mod tests {
 
         let mut cmd_str = to_cmd_str!(r#""Test Aircraft" REG AB1234 PRA 0000 AB123 KBN"#);
         assert!(
-            get_flight(&mut cmd_str, air_facilities).is_ok(),
+            get_flight(&mut cmd_str, air_facilities, &Fleet::default()).is_ok(),
             "Unsuccessful flight parsing"
         );
         Ok(())
@@ -292,7 +651,7 @@ mod tests {
 
         let mut cmd_str = to_cmd_str!(r#""Test Aircraft" REG AB1234 PRA 0000 AB123 KBN 0000"#);
         assert!(
-            get_flight(&mut cmd_str, air_facilities).is_ok(),
+            get_flight(&mut cmd_str, air_facilities, &Fleet::default()).is_ok(),
             "Unsuccessful flight parsing"
         );
         Ok(())
@@ -305,7 +664,7 @@ mod tests {
         let mut cmd_str =
             to_cmd_str!(r#""Test Aircraft" REG AB1234 PRA 0000 AB123 KBN 0000 AB1234 MLH"#);
         assert!(
-            get_flight(&mut cmd_str, air_facilities).is_ok(),
+            get_flight(&mut cmd_str, air_facilities, &Fleet::default()).is_ok(),
             "Unsuccessful flight parsing"
         );
         Ok(())
@@ -317,9 +676,15 @@ mod tests {
 
         let mut cmd_str =
             to_cmd_str!(r#""Test Aircraft" REG AB1234 PRA 0000 AB123 KBN _ AB1234 MLH _"#);
-        assert!(
-            get_flight(&mut cmd_str, air_facilities).is_ok(),
-            "Unsuccessful flight parsing"
+        let flight = get_flight(&mut cmd_str, air_facilities, &Fleet::default())?;
+        assert_eq!(
+            flight
+                .segments
+                .iter()
+                .map(|seg| seg.depart_time.to_string())
+                .join(" "),
+            "0000 1721 0314+1",
+            "Faulty estimation"
         );
         Ok(())
     }
@@ -329,7 +694,7 @@ mod tests {
         let (air_facilities, _) = test_setup()?;
 
         let mut cmd_str = to_cmd_str!(r#""Test Aircraft" REG"#);
-        assert!(get_flight(&mut cmd_str, air_facilities).is_ok());
+        assert!(get_flight(&mut cmd_str, air_facilities, &Fleet::default()).is_ok());
         Ok(())
     }
 
@@ -338,7 +703,7 @@ mod tests {
         let (air_facilities, _) = test_setup()?;
 
         let mut cmd_str = to_cmd_str!(r#""Test Aircraft" REG AB1234 PRA"#);
-        assert!(get_flight(&mut cmd_str, air_facilities).is_err());
+        assert!(get_flight(&mut cmd_str, air_facilities, &Fleet::default()).is_err());
         Ok(())
     }
 
@@ -347,10 +712,17 @@ mod tests {
         let (air_facilities, _) = test_setup()?;
 
         let mut cmd_str = to_cmd_str!(r#""Test Aircraft" REG AB1234"#);
-        assert!(get_flight(&mut cmd_str, air_facilities).is_err());
+        assert!(get_flight(&mut cmd_str, air_facilities, &Fleet::default()).is_err());
         Ok(())
     }
 
+    #[test]
+    pub fn duration_sign() {
+        assert_eq!(duration(65), "1h05", "Faulty duration");
+        assert_eq!(duration(-65), "-1h05", "Faulty negative duration");
+        assert_eq!(duration(-5), "-0h05", "Faulty negative duration");
+    }
+
     #[test]
     pub fn get_lowercase_airport_code() -> Result<()> {
         let mut cmd_str = to_cmd_str!("abc");
@@ -0,0 +1,65 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::Result;
+use common::data_types::timetable::AirlineTimetable;
+
+use crate::{arg, cmds::bulk, filter::Filter, Action};
+
+pub fn ba(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &AirlineTimetable,
+    filter: &Filter,
+) -> Result<Action> {
+    let old = arg!(cmd_str "old_aircraft" get_aircraft)?;
+    let new = arg!(cmd_str "new_aircraft" get_aircraft)?;
+    bulk(file, filter, |flight| {
+        if flight.aircraft == old {
+            flight.aircraft = new.to_owned();
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use smol_str::SmolStr;
+
+    use crate::{ba, cmds::test_setup, filter::Filter, to_cmd_str, Action};
+
+    #[test]
+    fn ba_normal() -> Result<()> {
+        let (_, file) = test_setup()?;
+        let mut cmd_str = to_cmd_str!(r#""Test" "New Aircraft""#);
+        let Action::Confirm(_, flights) = ba(&mut cmd_str, &file, &Filter::default())? else {
+            panic!("Unsuccessful aircraft swap");
+        };
+        assert_eq!(
+            flights[0].aircraft,
+            SmolStr::from("New Aircraft"),
+            "Faulty aircraft swap"
+        );
+        Ok(())
+    }
+
+    macro_rules! assert_err {
+        ($fn_name:ident, $cmd:literal) => {
+            #[test]
+            fn $fn_name() -> Result<()> {
+                let (_, file) = test_setup()?;
+                let mut cmd_str = to_cmd_str!($cmd);
+                assert!(
+                    ba(&mut cmd_str, &file, &Filter::default()).is_err(),
+                    "`{}` did not error",
+                    stringify!($fn_name)
+                );
+                Ok(())
+            }
+        };
+    }
+
+    assert_err!(ba_no_old, "");
+    assert_err!(ba_no_new, r#""Test""#);
+    assert_err!(ba_no_quotes, "Test New");
+    assert_err!(ba_no_match, r#""Other" "New""#);
+}
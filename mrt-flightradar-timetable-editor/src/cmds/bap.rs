@@ -0,0 +1,71 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::Result;
+use common::data_types::timetable::AirlineTimetable;
+
+use crate::{arg, cmds::bulk, filter::Filter, Action};
+
+pub fn bap(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &AirlineTimetable,
+    filter: &Filter,
+) -> Result<Action> {
+    let old = arg!(cmd_str "old_airport" get_airport)?;
+    let new = arg!(cmd_str "new_airport" get_airport)?;
+    bulk(file, filter, |flight| {
+        for seg in &mut flight.segments {
+            if seg.airport == old {
+                seg.airport = new.to_owned();
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use smol_str::SmolStr;
+
+    use crate::{bap, cmds::test_setup, filter::Filter, to_cmd_str, Action};
+
+    #[test]
+    fn bap_normal() -> Result<()> {
+        let (_, file) = test_setup()?;
+        let mut cmd_str = to_cmd_str!("def XYZ");
+        let Action::Confirm(_, flights) = bap(&mut cmd_str, &file, &Filter::default())? else {
+            panic!("Unsuccessful airport rename");
+        };
+        assert_eq!(
+            flights[0].segments[1].airport,
+            SmolStr::from("XYZ"),
+            "Faulty airport rename"
+        );
+        assert_eq!(
+            flights[0].segments[0].airport,
+            SmolStr::from("ABC"),
+            "Faulty airport rename"
+        );
+        Ok(())
+    }
+
+    macro_rules! assert_err {
+        ($fn_name:ident, $cmd:literal) => {
+            #[test]
+            fn $fn_name() -> Result<()> {
+                let (_, file) = test_setup()?;
+                let mut cmd_str = to_cmd_str!($cmd);
+                assert!(
+                    bap(&mut cmd_str, &file, &Filter::default()).is_err(),
+                    "`{}` did not error",
+                    stringify!($fn_name)
+                );
+                Ok(())
+            }
+        };
+    }
+
+    assert_err!(bap_no_old, "");
+    assert_err!(bap_no_new, "ABC");
+    assert_err!(bap_no_match, "XYZ ABC");
+}
@@ -0,0 +1,95 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::timetable::AirlineTimetable;
+use regex::Regex;
+
+use crate::{arg, cmds::bulk, filter::Filter, Action};
+
+pub fn bf(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &AirlineTimetable,
+    filter: &Filter,
+) -> Result<Action> {
+    let pattern = arg!(cmd_str "pattern" get_str)?;
+    let start = match cmd_str.next() {
+        Some(start) => {
+            let Ok(start) = start.parse::<usize>() else {
+                return Err(eyre!("Invalid start number `{start}`"));
+            };
+            start
+        }
+        None => 1,
+    };
+    let hashes = Regex::new("#+")?.find(&pattern).map(|m| m.as_str());
+    let Some((prefix, suffix)) = hashes.and_then(|hashes| pattern.split_once(hashes)) else {
+        return Err(eyre!("Pattern `{pattern}` has no `#` for the number"));
+    };
+    let width = hashes.map_or(0, str::len);
+    // flight numbers can only be read back if they are letters, digits and underscores
+    let word = Regex::new(r"^\w*$")?;
+    if !word.is_match(prefix) || !word.is_match(suffix) {
+        return Err(eyre!(
+            "Pattern `{pattern}` can only have letters, digits and `_` around the `#`"
+        ));
+    }
+
+    let mut number = start;
+    bulk(file, filter, |flight| {
+        for seg in &mut flight.segments {
+            seg.flight_no = format!("{prefix}{number:0width$}{suffix}").into();
+            number += 1;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use smol_str::SmolStr;
+
+    use crate::{bf, cmds::test_setup, filter::Filter, to_cmd_str, Action};
+
+    #[test]
+    fn bf_normal() -> Result<()> {
+        let (_, file) = test_setup()?;
+        let mut cmd_str = to_cmd_str!("XY### 8");
+        let Action::Confirm(_, flights) = bf(&mut cmd_str, &file, &Filter::default())? else {
+            panic!("Unsuccessful renumber");
+        };
+        assert_eq!(
+            flights[0].segments[0].flight_no,
+            SmolStr::from("XY008"),
+            "Faulty renumber"
+        );
+        assert_eq!(
+            flights[0].segments[1].flight_no,
+            SmolStr::from("XY009"),
+            "Faulty renumber"
+        );
+        Ok(())
+    }
+
+    macro_rules! assert_err {
+        ($fn_name:ident, $cmd:literal) => {
+            #[test]
+            fn $fn_name() -> Result<()> {
+                let (_, file) = test_setup()?;
+                let mut cmd_str = to_cmd_str!($cmd);
+                assert!(
+                    bf(&mut cmd_str, &file, &Filter::default()).is_err(),
+                    "`{}` did not error",
+                    stringify!($fn_name)
+                );
+                Ok(())
+            }
+        };
+    }
+
+    assert_err!(bf_no_pattern, "");
+    assert_err!(bf_no_hash, "XY");
+    assert_err!(bf_invalid_start, "XY# a");
+    assert_err!(bf_invalid_prefix, "XY-###");
+    assert_err!(bf_invalid_suffix, "XY###,");
+}
@@ -0,0 +1,70 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::timetable::AirlineTimetable;
+
+use crate::{arg, cmds::bulk, filter::Filter, Action};
+
+pub fn bs(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &AirlineTimetable,
+    filter: &Filter,
+) -> Result<Action> {
+    let minutes = arg!(cmd_str "minutes" get_str)?;
+    let Ok(minutes) = minutes.parse::<i32>() else {
+        return Err(eyre!("Invalid number of minutes `{minutes}`"));
+    };
+    bulk(file, filter, |flight| {
+        flight.shift(minutes);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use smol_str::SmolStr;
+
+    use crate::{bs, cmds::test_setup, filter::Filter, to_cmd_str, Action};
+
+    #[test]
+    fn bs_normal() -> Result<()> {
+        let (_, file) = test_setup()?;
+        let mut cmd_str = to_cmd_str!("-30");
+        let Action::Confirm(preview, flights) = bs(&mut cmd_str, &file, &Filter::default())? else {
+            panic!("Unsuccessful shift");
+        };
+        assert!(preview.contains("1 flight(s)"), "Faulty preview");
+        assert_eq!(
+            flights[0].segments[0].depart_time.to_string(),
+            "2330",
+            "Faulty shift"
+        );
+        assert_eq!(
+            flights[0].segments[1].flight_no,
+            SmolStr::from("CD456"),
+            "Faulty shift"
+        );
+        Ok(())
+    }
+
+    macro_rules! assert_err {
+        ($fn_name:ident, $cmd:literal) => {
+            #[test]
+            fn $fn_name() -> Result<()> {
+                let (_, file) = test_setup()?;
+                let mut cmd_str = to_cmd_str!($cmd);
+                assert!(
+                    bs(&mut cmd_str, &file, &Filter::default()).is_err(),
+                    "`{}` did not error",
+                    stringify!($fn_name)
+                );
+                Ok(())
+            }
+        };
+    }
+
+    assert_err!(bs_no_minutes, "");
+    assert_err!(bs_invalid_minutes, "1h");
+    assert_err!(bs_no_change, "0");
+}
@@ -1,7 +1,8 @@
 use std::{iter::Peekable, str::Split};
 
+use chrono::NaiveDate;
 use color_eyre::eyre::{eyre, Result};
-use common::data_types::timetable::AirlineTimetable;
+use common::data_types::timetable::{AirlineTimetable, DATE_FORMAT};
 use itertools::Itertools;
 use regex::Regex;
 
@@ -20,36 +21,67 @@ pub fn c(cmd_str: &mut Peekable<Split<char>>, file: &mut AirlineTimetable) -> Re
     if field == "a" && value.contains('"') {
         return Err(eyre!("Aircraft cannot contain `\"`"));
     }
+    let mut flight = file.flights[index].to_owned();
     match field {
-        "a" => file.flights[index].aircraft = value.into(),
-        "reg" => file.flights[index].registry = value.into(),
+        "a" => flight.aircraft = value.into(),
+        "reg" => flight.registry = value.into(),
+        "days" => flight.days = value.parse()?,
+        "from" | "to" => {
+            let date = if value == "-" {
+                None
+            } else {
+                Some(
+                    NaiveDate::parse_from_str(&value, DATE_FORMAT)
+                        .ok()
+                        .ok_or_else(|| eyre!("Invalid date `{value}`, expected YYYY-MM-DD"))?,
+                )
+            };
+            if field == "from" {
+                flight.valid_from = date;
+            } else {
+                flight.valid_to = date;
+            }
+        }
         field => {
             if let Some(re) = Regex::new(r"^a(\d+)$")?.captures(field) {
                 let idx = re.get(1).unwrap().as_str().parse::<usize>()? - 1;
-                if let Some(seg) = file.flights[index].segments.get_mut(idx) {
-                    seg.airport = value.into();
+                if let Some(seg) = flight.segments.get_mut(idx) {
+                    seg.airport = value.to_uppercase().into();
                 } else {
                     return Err(eyre!("No index {idx}"));
                 }
             } else if let Some(re) = Regex::new(r"^f(\d+)$")?.captures(field) {
                 let idx = re.get(1).unwrap().as_str().parse::<usize>()? - 1;
-                if let Some(seg) = file.flights[index].segments.get_mut(idx) {
+                if let Some(seg) = flight.segments.get_mut(idx) {
                     seg.flight_no = value.into();
                 } else {
                     return Err(eyre!("No index {idx}"));
                 }
             } else if let Some(re) = Regex::new(r"^d(\d+)$")?.captures(field) {
                 let idx = re.get(1).unwrap().as_str().parse::<usize>()? - 1;
-                if let Some(seg) = file.flights[index].segments.get_mut(idx) {
+                if let Some(seg) = flight.segments.get_mut(idx) {
                     seg.depart_time = value.parse()?;
                 } else {
                     return Err(eyre!("No index {idx}"));
                 }
+            } else if let Some(re) = Regex::new(r"^r(\d+)$")?.captures(field) {
+                let idx = re.get(1).unwrap().as_str().parse::<usize>()? - 1;
+                if let Some(seg) = flight.segments.get_mut(idx) {
+                    seg.arrive_time = if value == "-" {
+                        None
+                    } else {
+                        Some(value.parse()?)
+                    };
+                } else {
+                    return Err(eyre!("No index {idx}"));
+                }
             } else {
                 return Err(eyre!("Invalid field name `{field}`"));
             }
         }
     }
+    flight.validate()?;
+    file.flights[index] = flight;
     Ok(Action::Refresh)
 }
 
@@ -58,7 +90,11 @@ mod tests {
     use color_eyre::eyre::Result;
     use smol_str::SmolStr;
 
-    use crate::{c, cmds::test_setup, to_cmd_str, Action};
+    use crate::{
+        c,
+        cmds::{schedule, test_setup},
+        to_cmd_str, Action,
+    };
 
     #[test]
     fn c_normal() -> Result<()> {
@@ -88,7 +124,7 @@ mod tests {
             "Faulty set for `reg`"
         );
 
-        let mut cmd_str = to_cmd_str!("0 a2 PQR");
+        let mut cmd_str = to_cmd_str!("0 a2 pqr");
         assert_eq!(
             c(&mut cmd_str, &mut file).unwrap(),
             Action::Refresh,
@@ -126,6 +162,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn c_schedule() -> Result<()> {
+        let (_, mut file) = test_setup()?;
+        c(&mut to_cmd_str!("0 days 135"), &mut file)?;
+        c(&mut to_cmd_str!("0 from 2026-06-01"), &mut file)?;
+        c(&mut to_cmd_str!("0 to 2026-08-31"), &mut file)?;
+        assert_eq!(
+            schedule(&file.flights[0]),
+            "1.3.5.. 2026-06-01..2026-08-31",
+            "Faulty set for schedule"
+        );
+        assert!(
+            c(&mut to_cmd_str!("0 from 2026-09-01"), &mut file).is_err(),
+            "Validity ending before it starts did not error"
+        );
+        c(&mut to_cmd_str!("0 from -"), &mut file)?;
+        assert_eq!(file.flights[0].valid_from, None, "Faulty clear for `from`");
+        Ok(())
+    }
+
+    #[test]
+    fn c_arrival() -> Result<()> {
+        let (_, mut file) = test_setup()?;
+        c(&mut to_cmd_str!("0 r2 0930"), &mut file)?;
+        assert_eq!(
+            file.flights[0].segments[1].arrive_time,
+            Some("0930".parse()?),
+            "Faulty set for `r2`"
+        );
+        assert!(
+            c(&mut to_cmd_str!("0 d2 0900"), &mut file).is_err(),
+            "Departure before arrival did not error"
+        );
+        assert_eq!(
+            file.flights[0].segments[1].depart_time,
+            "1000".parse()?,
+            "Invalid change was kept"
+        );
+        c(&mut to_cmd_str!("0 r2 -"), &mut file)?;
+        assert_eq!(
+            file.flights[0].segments[1].arrive_time, None,
+            "Faulty clear for `r2`"
+        );
+        Ok(())
+    }
+
     macro_rules! assert_err {
         ($fn_name:ident, $cmd:literal) => {
             #[test]
@@ -151,4 +233,8 @@ mod tests {
     assert_err!(c_out_of_index_2, "0 d4 0000");
     assert_err!(c_out_of_index_3, "0 f4 AB1234");
     assert_err!(c_field_nonexistent, "0 foo bar");
+    assert_err!(c_invalid_days, "0 days 8");
+    assert_err!(c_invalid_date, "0 to 2026-02-30");
+    assert_err!(c_arrival_first_segment, "0 r1 0000");
+    assert_err!(c_arrival_after_departure, "0 r2 1030");
 }
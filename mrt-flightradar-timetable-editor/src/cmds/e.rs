@@ -1,41 +1,131 @@
 use std::{iter::Peekable, str::Split};
 
 use color_eyre::eyre::Result;
-use common::data_types::{airport::AirFacility, vec::Pos};
+use common::{
+    data_types::{
+        airport::{AirFacility, Runway},
+        time::TIME_SCALE,
+        vec::{FromLoc, Pos},
+        RAW_DATA,
+    },
+    flight_route::{
+        get_estimated_route,
+        types::path::{FlightPath, Path},
+    },
+};
 use glam::Vec2;
+use smol_str::SmolStr;
 
-use crate::{arg, cmds::get_main_coord, Action};
+use crate::{
+    arg,
+    cmds::{get_air_facility, get_main_coord},
+    Action,
+};
 
 pub fn e(cmd_str: &mut Peekable<Split<char>>, air_facilities: &[AirFacility]) -> Result<Action> {
     let a1 = arg!(cmd_str "a1" get_str)?;
     let d1 = arg!(cmd_str "d1" get_time)?;
     let a2 = arg!(cmd_str "a2" get_str)?;
-    let c1 = get_main_coord(&a1, air_facilities)?;
-    let c2 = get_main_coord(&a2, air_facilities)?;
-    let time = estimate_time(*c1, *c2);
+    let (time, routed) = estimate_time(&a1, &a2, air_facilities)?;
     let d2 = d1 + time;
     Ok(Action::Msg(format!(
-        "Flight arrives at {d2} after {time:.2} hours"
+        "Flight arrives at {d2} after {time:.2} hours{}",
+        if routed {
+            ""
+        } else {
+            " (flying direct, as there is no route)"
+        }
     )))
 }
 
-pub fn estimate_time(c1: Pos<Vec2>, c2: Pos<Vec2>) -> f32 {
-    (((c2.x - c1.x) + (c2.y - c1.y)) / 5000.0).abs()
+/// Runway pointing closest to `dir`
+fn best_runway(runways: &[Runway], dir: Vec2) -> Option<&Runway> {
+    runways.iter().max_by(|r1, r2| {
+        let dot = |r: &Runway| r.vec.vec.normalize_or_zero().dot(dir);
+        dot(r1).total_cmp(&dot(r2))
+    })
+}
+
+/// Real seconds taken to fly straight from `c1` to `c2`
+fn direct_time(c1: Pos<Vec2>, c2: Pos<Vec2>) -> f32 {
+    FlightPath(vec![Path::Straight(FromLoc::new(c1, c2))]).time_taken()
+}
+
+/// Estimates the flight time between two air facilities in in-game hours, by routing along airways
+/// the same way the server does but without its random offsets. Falls back to flying direct if either facility has no runways
+/// or there is no route, in which case the second value is `false`
+pub fn estimate_time(
+    a1: &SmolStr,
+    a2: &SmolStr,
+    air_facilities: &[AirFacility],
+) -> Result<(f32, bool)> {
+    let (c1, c2) = (
+        get_main_coord(a1, air_facilities)?,
+        get_main_coord(a2, air_facilities)?,
+    );
+    let dir = (c2 - c1).normalize_or_zero();
+    let routed = match (
+        get_air_facility(a1, air_facilities)?,
+        get_air_facility(a2, air_facilities)?,
+    ) {
+        (AirFacility::Airport { runways: r1, .. }, AirFacility::Airport { runways: r2, .. }) => {
+            best_runway(r1, dir)
+                .zip(best_runway(r2, dir))
+                .and_then(|(start, end)| get_estimated_route(&RAW_DATA.get(), start, end).ok())
+                .map(|path| path.time_taken())
+        }
+        _ => None,
+    };
+    let seconds = routed.unwrap_or_else(|| direct_time(c1, c2));
+    Ok((seconds * TIME_SCALE / 3600.0, routed.is_some()))
 }
 
 #[cfg(test)]
 mod tests {
     use color_eyre::eyre::Result;
+    use common::{
+        data_types::{airport::AirFacility, time::TIME_SCALE},
+        flight_route::types::path::{ACCEL, MAX_SPEED},
+    };
+    use glam::vec2;
 
-    use crate::{cmds::test_setup, e, to_cmd_str, Action};
+    use crate::{
+        cmds::{e::estimate_time, test_setup},
+        e, to_cmd_str, Action,
+    };
 
     #[test]
     fn e_normal() -> Result<()> {
         let (air_facilities, _) = test_setup()?;
         let mut cmd_str = to_cmd_str!("PRA 0000 KBN");
+        assert_eq!(
+            e(&mut cmd_str, air_facilities)?,
+            Action::Msg("Flight arrives at 1721 after 17.35 hours".into()),
+            "Faulty estimation"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn estimate_time_direct() -> Result<()> {
+        let air_facilities = [
+            AirFacility::Heliport {
+                code: "H1".into(),
+                pad_coord: vec2(0.0, 0.0),
+                name: None,
+            },
+            AirFacility::Heliport {
+                code: "H2".into(),
+                pad_coord: vec2(3000.0, 4000.0),
+                name: None,
+            },
+        ];
+        let (time, routed) = estimate_time(&"H1".into(), &"H2".into(), &air_facilities)?;
+        assert!(!routed, "Heliports were routed");
+        // 5000 blocks at cruising speed, plus the time spent accelerating and decelerating
         assert!(
-            matches!(e(&mut cmd_str, air_facilities).unwrap(), Action::Msg(_)),
-            "Unsuccessful estimation"
+            (time - (5000.0 / MAX_SPEED + MAX_SPEED / ACCEL) * TIME_SCALE / 3600.0).abs() < 1e-3,
+            "Faulty direct estimation"
         );
         Ok(())
     }
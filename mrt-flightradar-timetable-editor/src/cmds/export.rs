@@ -0,0 +1,62 @@
+use std::{fs, iter::Peekable, path::Path, str::Split};
+
+use chrono::Utc;
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::timetable::AirlineTimetable;
+
+use crate::{cmds::get_path, Action};
+
+pub fn export(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &AirlineTimetable,
+    file_path: &Path,
+) -> Result<Action> {
+    let path = get_path(cmd_str, file_path)?;
+    let cont = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("csv") => file.to_csv()?,
+        Some("json") => file.to_json()?,
+        Some("ics") => file.to_ical(Utc::now()),
+        _ => return Err(eyre!("Unknown format of `{}`", path.display())),
+    };
+    fs::write(&path, cont)?;
+    Ok(Action::Msg(format!(
+        "Exported {} flight(s) to {}",
+        file.flights.len(),
+        path.display()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use color_eyre::eyre::Result;
+    use common::data_types::timetable::AirlineTimetable;
+
+    use crate::{
+        cmds::{test_setup, TestDir},
+        export,
+    };
+
+    #[test]
+    fn export_normal() -> Result<()> {
+        let (_, file) = test_setup()?;
+        let dir = TestDir::new()?;
+        let path = dir.join("Test.fpln");
+        let [json, ics, txt] = ["out.json", "out.ics", "out.txt"]
+            .map(|name| export(&mut name.split(' ').peekable(), &file, &path).map(|_| ()));
+        json?;
+        ics?;
+        assert!(txt.is_err(), "Unknown format did not error");
+        assert_eq!(
+            AirlineTimetable::from_json(&fs::read_to_string(dir.join("out.json"))?)?,
+            file,
+            "Faulty export"
+        );
+        assert!(
+            fs::read_to_string(dir.join("out.ics"))?.starts_with("BEGIN:VCALENDAR"),
+            "Faulty export"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::{eyre, Result};
+use itertools::Itertools;
+
+use crate::{
+    arg,
+    filter::{Criterion, Filter},
+    Action,
+};
+
+pub fn f(cmd_str: &mut Peekable<Split<char>>, filter: &mut Filter) -> Result<Action> {
+    let field = &*arg!(cmd_str "filter" get_str)?;
+    let criterion = match field {
+        "a" => {
+            let value = cmd_str.take_while(|_| true).join(" ");
+            let value = value.trim().trim_matches('"');
+            if value.is_empty() {
+                return Err(eyre!("Missing argument <aircraft>"));
+            }
+            Criterion::Aircraft(value.into())
+        }
+        "reg" => Criterion::Registry(arg!(cmd_str "registry" get_str)?),
+        "apt" => Criterion::Airport(arg!(cmd_str "airport" get_airport)?),
+        "f" => Criterion::flight_no(&arg!(cmd_str "pattern" get_str)?)?,
+        "d" => Criterion::Departure(
+            arg!(cmd_str "from" get_time)?.time_of_day(),
+            arg!(cmd_str "to" get_time)?.time_of_day(),
+        ),
+        "on" => Criterion::OperatesOn(arg!(cmd_str "date" get_date)?),
+        field => return Err(eyre!("Invalid filter `{field}`")),
+    };
+    filter.0.push(criterion);
+    Ok(Action::Refresh)
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use crate::{cmds::test_setup, f, filter::Filter, to_cmd_str, Action};
+
+    fn matches(cmd: &str) -> Result<Vec<usize>> {
+        let (_, file) = test_setup()?;
+        let mut filter = Filter::default();
+        assert_eq!(
+            f(&mut cmd.split(' ').peekable(), &mut filter)?,
+            Action::Refresh,
+            "Unsuccessful filter"
+        );
+        Ok(filter.apply(&file.flights).map(|(i, _)| i).collect())
+    }
+
+    #[test]
+    fn f_normal() -> Result<()> {
+        assert_eq!(matches("a tes")?, [0], "Faulty aircraft filter");
+        assert_eq!(matches("reg reg")?, [0], "Faulty registry filter");
+        assert_eq!(
+            matches("reg ABC-123")?,
+            Vec::<usize>::new(),
+            "Faulty registry filter"
+        );
+        assert_eq!(matches("apt def")?, [0], "Faulty airport filter");
+        assert_eq!(
+            matches("apt KBN")?,
+            Vec::<usize>::new(),
+            "Faulty airport filter"
+        );
+        assert_eq!(matches("f AB*")?, [0], "Faulty flight number filter");
+        assert_eq!(
+            matches("f AB?")?,
+            Vec::<usize>::new(),
+            "Faulty flight number filter"
+        );
+        assert_eq!(matches("d 0900 1100")?, [0], "Faulty departure filter");
+        assert_eq!(matches("d 2300 0100")?, [0], "Faulty departure filter");
+        assert_eq!(matches("d 2300+1 0100+1")?, [0], "Faulty departure filter");
+        assert_eq!(
+            matches("d 0100 0900")?,
+            Vec::<usize>::new(),
+            "Faulty departure filter"
+        );
+        assert_eq!(
+            matches("on 2026-06-01")?,
+            [0],
+            "Faulty operating day filter"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn f_keeps_indices() -> Result<()> {
+        let (_, mut file) = test_setup()?;
+        file.flights.insert(0, file.flights[0].to_owned());
+        file.flights[0].registry = "OTHER".into();
+        let mut filter = Filter::default();
+        f(&mut to_cmd_str!("reg REG"), &mut filter)?;
+        assert_eq!(
+            filter
+                .apply(&file.flights)
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>(),
+            [1],
+            "Faulty filter indices"
+        );
+        Ok(())
+    }
+
+    macro_rules! assert_err {
+        ($fn_name:ident, $cmd:literal) => {
+            #[test]
+            fn $fn_name() -> Result<()> {
+                let mut cmd_str = to_cmd_str!($cmd);
+                assert!(
+                    f(&mut cmd_str, &mut Filter::default()).is_err(),
+                    "`{}` did not error",
+                    stringify!($fn_name)
+                );
+                Ok(())
+            }
+        };
+    }
+
+    assert_err!(f_no_filter, "");
+    assert_err!(f_invalid_filter, "x ABC");
+    assert_err!(f_no_value, "reg");
+    assert_err!(f_no_to, "d 0000");
+    assert_err!(f_invalid_time, "d 0000 2");
+    assert_err!(f_invalid_date, "on 01/06/2026");
+}
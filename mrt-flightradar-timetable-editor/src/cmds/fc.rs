@@ -0,0 +1,24 @@
+use color_eyre::eyre::Result;
+
+use crate::{filter::Filter, Action};
+
+pub fn fc(filter: &mut Filter) -> Result<Action> {
+    filter.0.clear();
+    Ok(Action::Refresh)
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use crate::{f, fc, filter::Filter, to_cmd_str, Action};
+
+    #[test]
+    fn fc_normal() -> Result<()> {
+        let mut filter = Filter::default();
+        f(&mut to_cmd_str!("reg REG"), &mut filter)?;
+        assert_eq!(fc(&mut filter)?, Action::Refresh, "Unsuccessful clear");
+        assert!(filter.is_empty(), "Faulty clear");
+        Ok(())
+    }
+}
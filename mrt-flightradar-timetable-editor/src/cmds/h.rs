@@ -3,70 +3,174 @@ use color_eyre::eyre::Result;
 
 use crate::Action;
 
+pub const NOTES: &[&str] = &[
+    "<segment> = <flight_no> <airport> <depart_time>",
+    "<segments> = <flight_no1> <airport1> <depart_time1> [<flight_no2> <airport2> <depart_time2> [<3> [<4> [<etc>...]]]]",
+    "<depart_timeN> can be `_` for automatic estimation if N > 1",
+    "<depart_timeN> can be omitted for automatic estimation if N is last",
+    "Times after midnight of a flight's first day take a day offset, eg. `0100+1`",
+];
+
+pub const CMDS: &[(&str, &str, &str)] = &[
+    (
+        "q",
+        "[!]",
+        "Quit the editor. If autosave is off and there are unsaved changes, `!` quits without saving",
+    ),
+    ("w", "", "Save every airline that changed"),
+    (
+        "wa",
+        "[on|off]",
+        "Turn saving after every command on or off, or toggle it",
+    ),
+    ("h", "", "View this page"),
+    (
+        "i",
+        "<index> \"<aircraft>\" <reg> <segment>",
+        "Insert flight into buffer (Aircraft must be in quotes, and may be left out if <reg> is in the airline's fleet file)",
+    ),
+    (
+        "is",
+        "\"<aircraft>\" <reg> <segments>",
+        "Add flight to start of buffer",
+    ),
+    (
+        "ie",
+        "\"<aircraft>\" <reg> <segments>",
+        "Add flight to end of buffer",
+    ),
+    (
+        "c",
+        "<index> <field> <new_value>",
+        "Change value of field of flight in buffer. <field> is `a`, `reg`, `days` (eg. `12345..`, 1 is Monday), `from` or `to` (YYYY-MM-DD validity dates, `-` to clear), or `fN`, `aN`, `dN` or `rN` (arrival, `-` to clear) of segment N",
+    ),
+    ("d", "<index>", "Remove flight from buffer"),
+    (
+        "m",
+        "<index1> <index2>",
+        "Move a flight at index1 to index2",
+    ),
+    (
+        "e",
+        "<a1> <d1> <a2>",
+        "Estimate an arrival time for a flight",
+    ),
+    (
+        "n",
+        "<airport>",
+        "Get the airport name, given the code. Otherwise list the airports whose name matches <airport>, or whose code starts with it if it ends with `*`",
+    ),
+    (
+        "nc",
+        "",
+        "List air facilities in the raw data without a name, and named airports that aren't in the raw data",
+    ),
+    (
+        "import",
+        "<path>",
+        "Replace the flights with those of a `.csv` (one row per segment) or `.json` file. Relative paths are from the timetable's directory",
+    ),
+    (
+        "export",
+        "<path>",
+        "Write the timetable to a `.csv`, `.json` or `.ics` (iCalendar) file. Relative paths are from the timetable's directory",
+    ),
+    (
+        "f",
+        "<filter> <value>",
+        "Only show flights matching the filter, on top of the current filters. <filter> is `a` (aircraft contains), `reg`, `apt` (airport), `f` (flight number, `*` and `?` are wildcards), `d` (departure window, <value> = <from> <to>) or `on` (operating on a YYYY-MM-DD date)",
+    ),
+    ("fc", "", "Clear the filters"),
+    (
+        "st",
+        "[<path>]",
+        "Show statistics of the timetable: flights per airport, busiest hours, routes, aircraft utilisation and unserved airports. With <path>, export them to a `.json` file instead, relative to the timetable's directory",
+    ),
+    ("u", "", "Undo the last change to the timetable"),
+    (
+        "ws",
+        "[<airline>]",
+        "List the airlines in the workspace, or switch to another one. Switching clears the filters and undo history",
+    ),
+    (
+        "wm",
+        "<index> <airline>",
+        "Move a flight to the end of another airline in the workspace. This clears the undo history",
+    ),
+    (
+        "wcp",
+        "<index> <airline>",
+        "Copy a flight to the end of another airline in the workspace",
+    ),
+    (
+        "wc",
+        "",
+        "Check the workspace for registries and flight numbers used by more than one airline, and departures from the same airport at the same time",
+    ),
+    (
+        "re",
+        "<index> [<turnaround>] [!]",
+        "Re-estimate the departure times of a flight after the first, with at least <turnaround> minutes on the ground (default 15). Times that are still possible are kept, unless `!` is given",
+    ),
+    (
+        "rc",
+        "[<min_turnaround>]",
+        "Check that each registry's flights follow on from each other: every flight departs from where the last one arrived, after it arrived, with at least <min_turnaround> minutes on the ground (default 15)",
+    ),
+    (
+        "r",
+        "<index> <count> <interval> [<step>]",
+        "Repeat a flight <count> times, every <interval> minutes. Flight numbers go up by <step> each time (default the number of segments)",
+    ),
+    (
+        "bs",
+        "<minutes>",
+        "Shift every departure time of the filtered flights by a number of minutes, which may be negative",
+    ),
+    (
+        "ba",
+        "\"<old_aircraft>\" \"<new_aircraft>\"",
+        "Swap an aircraft for another across the filtered flights",
+    ),
+    (
+        "bap",
+        "<old_airport> <new_airport>",
+        "Rename an airport code across the filtered flights",
+    ),
+    (
+        "bf",
+        "<pattern> [<start>]",
+        "Renumber every segment of the filtered flights in order. The run of `#` in <pattern> is replaced by the number, counting from <start> (default 1)",
+    ),
+    (
+        "sa",
+        "<index> <segment_index> <segment>",
+        "Insert flight segment into flight segment list. <depart_time> may be omitted if <segment_index> != 0",
+    ),
+    (
+        "sae",
+        "<index> <segment>",
+        "Add flight segment to end of flight segment list. <depart_time> may be omitted if length of segment list != 0",
+    ),
+    (
+        "sas",
+        "<index> <segment>",
+        "Add flight segment to start of flight segment list",
+    ),
+    (
+        "sd",
+        "<index> <segment_index>",
+        "Remove flight segment from flight segment list",
+    ),
+];
+
 pub fn h() -> Result<Action> {
     println!("{$yellow+bold}Help menu{/$}");
-    println!("{$yellow}Note:{/$} <segment> = <flight_no> <airport> <depart_time>");
-    println!("{$yellow}Note:{/$} <segments> = <flight_no1> <airport1> <depart_time1> [<flight_no2> <airport2> <depart_time2> [<3> [<4> [<etc>...]]]]");
-    println!("{$yellow}Note:{/$} <depart_time{$cyan}n{/$}> can be `_` for automatic estimation if {$cyan}n{/$} > 1");
-    println!("{$yellow}Note:{/$} <depart_time{$cyan}n{/$}> can be omitted for automatic estimation if {$cyan}n{/$} is last");
+    for note in NOTES {
+        println!("{$yellow}Note:{/$} {}", note);
+    }
     println!();
-    let cmds = [
-        ("q", "", "Quit the editor"),
-        ("h", "", "View this page"),
-        (
-            "i",
-            "<index> \"<aircraft>\" <reg> <segment>",
-            "Insert flight into buffer (Aircraft must be in quotes)",
-        ),
-        (
-            "is",
-            "\"<aircraft>\" <reg> <segments>",
-            "Add flight to start of buffer",
-        ),
-        (
-            "ie",
-            "\"<aircraft>\" <reg> <segments>",
-            "Add flight to end of buffer",
-        ),
-        (
-            "c",
-            "<index> <field> <new_value>",
-            "Change value of field of flight in buffer",
-        ),
-        ("d", "<index>", "Remove flight from buffer"),
-        (
-            "m",
-            "<index1> <index2>",
-            "Move a flight at index1 to index2",
-        ),
-        (
-            "e",
-            "<a1> <d1> <a2>",
-            "Estimate an arrival time for a flight",
-        ),
-        ("n", "<airport>", "Get the airport name, given the code"),
-        (
-            "sa",
-            "<index> <segment_index> <segment>",
-            "Insert flight segment into flight segment list. <depart_time> may be omitted if <segment_index> != 0",
-        ),
-        (
-            "sae",
-            "<index> <segment>",
-            "Add flight segment to end of flight segment list. <depart_time> may be omitted if length of segment list != 0",
-        ),
-        (
-            "sas",
-            "<index> <segment>",
-            "Add flight segment to start of flight segment list",
-        ),
-        (
-            "sd",
-            "<index> <segment_index>",
-            "Remove flight segment from flight segment list",
-        ),
-    ];
-    for (cmd, args, desc) in cmds {
+    for (cmd, args, desc) in CMDS {
         println!("{[cyan+bold]} {[yellow]}\n{}", cmd, args, desc);
     }
     Ok(Action::Hold)
@@ -1,7 +1,7 @@
 use std::{iter::Peekable, str::Split};
 
 use color_eyre::eyre::Result;
-use common::data_types::{airport::AirFacility, timetable::AirlineTimetable};
+use common::data_types::{airport::AirFacility, fleet::Fleet, timetable::AirlineTimetable};
 
 use crate::{arg, Action};
 
@@ -9,9 +9,10 @@ pub fn i(
     cmd_str: &mut Peekable<Split<char>>,
     file: &mut AirlineTimetable,
     air_facilities: &[AirFacility],
+    fleet: &Fleet,
 ) -> Result<Action> {
     let index = arg!(cmd_str "index" get_index, file, le)?;
-    let flight = arg!(cmd_str "flight" get_flight, air_facilities)?;
+    let flight = arg!(cmd_str "flight" get_flight, air_facilities, fleet)?;
     file.flights.insert(index, flight);
     Ok(Action::Refresh)
 }
@@ -19,6 +20,7 @@ pub fn i(
 #[cfg(test)]
 mod tests {
     use color_eyre::eyre::Result;
+    use common::data_types::fleet::Fleet;
     use smol_str::SmolStr;
 
     use crate::{cmds::test_setup, i, to_cmd_str, Action};
@@ -28,7 +30,7 @@ mod tests {
         let (air_facilities, mut file) = test_setup()?;
         let mut cmd_str = to_cmd_str!(r#"0 "Test Aircraft" REG AB1234 PRA 0000"#);
         assert_eq!(
-            i(&mut cmd_str, &mut file, air_facilities).unwrap(),
+            i(&mut cmd_str, &mut file, air_facilities, &Fleet::default()).unwrap(),
             Action::Refresh,
             "Unsuccessful insert"
         );
@@ -43,7 +45,7 @@ mod tests {
                 let (air_facilities, mut file) = test_setup()?;
                 let mut cmd_str = to_cmd_str!($cmd);
                 assert!(
-                    i(&mut cmd_str, &mut file, &air_facilities).is_err(),
+                    i(&mut cmd_str, &mut file, &air_facilities, &Fleet::default()).is_err(),
                     "`{}` did not error",
                     stringify!($fn_name)
                 );
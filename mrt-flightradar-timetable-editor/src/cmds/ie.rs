@@ -1,7 +1,7 @@
 use std::{iter::Peekable, str::Split};
 
 use color_eyre::eyre::Result;
-use common::data_types::{airport::AirFacility, timetable::AirlineTimetable};
+use common::data_types::{airport::AirFacility, fleet::Fleet, timetable::AirlineTimetable};
 
 use crate::{arg, Action};
 
@@ -9,8 +9,9 @@ pub fn ie(
     cmd_str: &mut Peekable<Split<char>>,
     file: &mut AirlineTimetable,
     air_facilities: &[AirFacility],
+    fleet: &Fleet,
 ) -> Result<Action> {
-    let flight = arg!(cmd_str "flight" get_flight, air_facilities)?;
+    let flight = arg!(cmd_str "flight" get_flight, air_facilities, fleet)?;
     file.flights.push(flight);
     Ok(Action::Refresh)
 }
@@ -18,6 +19,7 @@ pub fn ie(
 #[cfg(test)]
 mod tests {
     use color_eyre::eyre::Result;
+    use common::data_types::fleet::Fleet;
     use smol_str::SmolStr;
 
     use crate::{cmds::test_setup, ie, to_cmd_str, Action};
@@ -27,7 +29,7 @@ mod tests {
         let (air_facilities, mut file) = test_setup()?;
         let mut cmd_str = to_cmd_str!(r#""Test Aircraft" REG AB1234 PRA 0000"#);
         assert_eq!(
-            ie(&mut cmd_str, &mut file, air_facilities).unwrap(),
+            ie(&mut cmd_str, &mut file, air_facilities, &Fleet::default()).unwrap(),
             Action::Refresh,
             "Unsuccessful insert"
         );
@@ -35,6 +37,21 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn ie_fleet() -> Result<()> {
+        let (air_facilities, mut file) = test_setup()?;
+        let fleet = Fleet::from_string("REG\tStratus SA-1")?;
+        let mut cmd_str = to_cmd_str!("REG AB1234 PRA 0000");
+        ie(&mut cmd_str, &mut file, air_facilities, &fleet)?;
+        assert_eq!(file.flights[1].aircraft, SmolStr::from("Stratus SA-1"));
+        let mut cmd_str = to_cmd_str!("OTHER AB1234 PRA 0000");
+        assert!(
+            ie(&mut cmd_str, &mut file, air_facilities, &fleet).is_err(),
+            "Registry outside the fleet did not error"
+        );
+        Ok(())
+    }
+
     macro_rules! assert_err {
         ($fn_name:ident, $cmd:literal) => {
             #[test]
@@ -42,7 +59,7 @@ mod tests {
                 let (air_facilities, mut file) = test_setup()?;
                 let mut cmd_str = to_cmd_str!($cmd);
                 assert!(
-                    ie(&mut cmd_str, &mut file, &air_facilities).is_err(),
+                    ie(&mut cmd_str, &mut file, &air_facilities, &Fleet::default()).is_err(),
                     "`{}` did not error",
                     stringify!($fn_name)
                 );
@@ -0,0 +1,65 @@
+use std::{fs, iter::Peekable, path::Path, str::Split};
+
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::timetable::AirlineTimetable;
+
+use crate::{
+    cmds::{confirm, get_path},
+    Action,
+};
+
+pub fn import(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &AirlineTimetable,
+    file_path: &Path,
+) -> Result<Action> {
+    let path = get_path(cmd_str, file_path)?;
+    let cont = fs::read_to_string(&path)?;
+    let imported = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("csv") => AirlineTimetable::from_csv(&cont, file.name.to_owned())?,
+        Some("json") => AirlineTimetable::from_json(&cont)?,
+        _ => return Err(eyre!("Unknown format of `{}`", path.display())),
+    };
+    confirm(&file.flights, imported.flights)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use color_eyre::eyre::Result;
+
+    use crate::{
+        cmds::{test_setup, TestDir},
+        import, to_cmd_str, Action,
+    };
+
+    #[test]
+    fn import_normal() -> Result<()> {
+        let (_, mut file) = test_setup()?;
+        let dir = TestDir::new()?;
+        let mut other = file.to_owned();
+        other.flights[0].registry = "OTHER".into();
+        fs::write(dir.join("other.csv"), other.to_csv()?)?;
+        let Action::Confirm(preview, flights) =
+            import(&mut to_cmd_str!("other.csv"), &file, &dir.join("Test.fpln"))?
+        else {
+            panic!("Unsuccessful import");
+        };
+        assert!(preview.contains("1 flight(s)"), "Faulty preview");
+        file.flights = flights;
+        assert_eq!(file, other, "Faulty import");
+        Ok(())
+    }
+
+    #[test]
+    fn import_unknown_format() -> Result<()> {
+        let (_, file) = test_setup()?;
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        assert!(
+            import(&mut to_cmd_str!("Cargo.toml"), &file, &path).is_err(),
+            "Unknown format did not error"
+        );
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use std::{iter::Peekable, str::Split};
 
 use color_eyre::eyre::Result;
-use common::data_types::{airport::AirFacility, timetable::AirlineTimetable};
+use common::data_types::{airport::AirFacility, fleet::Fleet, timetable::AirlineTimetable};
 
 use crate::{arg, Action};
 
@@ -9,8 +9,9 @@ pub fn is(
     cmd_str: &mut Peekable<Split<char>>,
     file: &mut AirlineTimetable,
     air_facilities: &[AirFacility],
+    fleet: &Fleet,
 ) -> Result<Action> {
-    let flight = arg!(cmd_str "flight" get_flight, air_facilities)?;
+    let flight = arg!(cmd_str "flight" get_flight, air_facilities, fleet)?;
     file.flights.insert(0, flight);
     Ok(Action::Refresh)
 }
@@ -18,6 +19,7 @@ pub fn is(
 #[cfg(test)]
 mod tests {
     use color_eyre::eyre::Result;
+    use common::data_types::fleet::Fleet;
     use smol_str::SmolStr;
 
     use crate::{cmds::test_setup, is, to_cmd_str, Action};
@@ -27,7 +29,7 @@ mod tests {
         let (air_facilities, mut file) = test_setup()?;
         let mut cmd_str = to_cmd_str!(r#""Test Aircraft" REG AB1234 PRA 0000"#);
         assert_eq!(
-            is(&mut cmd_str, &mut file, air_facilities).unwrap(),
+            is(&mut cmd_str, &mut file, air_facilities, &Fleet::default()).unwrap(),
             Action::Refresh,
             "Unsuccessful insert"
         );
@@ -42,7 +44,7 @@ mod tests {
                 let (air_facilities, mut file) = test_setup()?;
                 let mut cmd_str = to_cmd_str!($cmd);
                 assert!(
-                    is(&mut cmd_str, &mut file, &air_facilities).is_err(),
+                    is(&mut cmd_str, &mut file, &air_facilities, &Fleet::default()).is_err(),
                     "`{}` did not error",
                     stringify!($fn_name)
                 );
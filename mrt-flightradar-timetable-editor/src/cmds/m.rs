@@ -19,6 +19,7 @@ pub fn m(cmd_str: &mut Peekable<Split<char>>, file: &mut AirlineTimetable) -> Re
 #[cfg(test)]
 mod tests {
     use color_eyre::eyre::Result;
+    use common::data_types::fleet::Fleet;
     use smol_str::SmolStr;
 
     use crate::{cmds::test_setup, i, m, to_cmd_str, Action};
@@ -27,7 +28,7 @@ mod tests {
     fn m_normal() -> Result<()> {
         let (air_facilities, mut file) = test_setup()?;
         let mut cmd_str = to_cmd_str!(r#"0 "Test Aircraft" REG AB1234 PRA 0000"#);
-        i(&mut cmd_str, &mut file, air_facilities).unwrap();
+        i(&mut cmd_str, &mut file, air_facilities, &Fleet::default()).unwrap();
         let mut cmd_str = to_cmd_str!("0 1");
         assert_eq!(
             m(&mut cmd_str, &mut file).unwrap(),
@@ -49,7 +50,7 @@ mod tests {
                 let (air_facilities, mut file) = test_setup()?;
                 let mut cmd_str = to_cmd_str!($cmd);
                 assert!(
-                    i(&mut cmd_str, &mut file, &air_facilities).is_err(),
+                    i(&mut cmd_str, &mut file, &air_facilities, &Fleet::default()).is_err(),
                     "`{}` did not error",
                     stringify!($fn_name)
                 );
@@ -1,17 +1,46 @@
 use std::{iter::Peekable, str::Split};
 
 use color_eyre::eyre::{eyre, Result};
+use common::data_types::airport_names::AIRPORT_NAMES;
+use itertools::Itertools;
 
-use crate::{airport_names::get_airport_names, arg, Action};
+use crate::Action;
+
+/// Number of airports listed for a search
+const MAX_RESULTS: usize = 10;
 
 pub fn n(cmd_str: &mut Peekable<Split<char>>) -> Result<Action> {
-    let airport = arg!(cmd_str "airport" get_airport)?;
-    let airport_names = get_airport_names()?;
-    let Some(name) = airport_names.get(&airport) else {
-        return Err(eyre!("Airport {airport} is not recorded"));
-    };
+    let query = cmd_str.join(" ");
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(eyre!("Missing argument <airport>"));
+    }
+    if let Some(name) = AIRPORT_NAMES.name(&query.to_uppercase()) {
+        return Ok(Action::Msg(format!("{} is {name}", query.to_uppercase())));
+    }
 
-    Ok(Action::Msg(format!("{airport} is {name}")))
+    let airports = if let Some(prefix) = query.strip_suffix('*') {
+        AIRPORT_NAMES.with_prefix(prefix).collect::<Vec<_>>()
+    } else {
+        AIRPORT_NAMES.search(query)
+    };
+    if airports.is_empty() {
+        return Err(eyre!("No recorded airport matches `{query}`"));
+    }
+    let more = airports.len().saturating_sub(MAX_RESULTS);
+    Ok(Action::Msg(format!(
+        "{}{}",
+        airports
+            .iter()
+            .take(MAX_RESULTS)
+            .map(|(code, name)| format!("{code}\t{name}"))
+            .join("\n"),
+        if more > 0 {
+            format!("\n... and {more} more")
+        } else {
+            String::new()
+        }
+    )))
 }
 
 #[cfg(test)]
@@ -30,6 +59,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn n_search() -> Result<()> {
+        let Action::Msg(msg) = n(&mut to_cmd_str!("akane ishuzu"))? else {
+            panic!("Unsuccessful search");
+        };
+        assert!(msg.starts_with("HMN\t"), "Faulty search: {msg}");
+        let Action::Msg(msg) = n(&mut to_cmd_str!("hm*"))? else {
+            panic!("Unsuccessful prefix search");
+        };
+        assert!(
+            msg.lines().all(|line| line.starts_with("HM")),
+            "Faulty prefix search: {msg}"
+        );
+        Ok(())
+    }
+
     macro_rules! assert_err {
         ($fn_name:ident, $cmd:literal) => {
             #[test]
@@ -47,4 +92,5 @@ mod tests {
 
     assert_err!(n_no_airport, "");
     assert_err!(n_unregisted_airport, "???");
+    assert_err!(n_no_prefix_match, "?*");
 }
@@ -0,0 +1,37 @@
+use color_eyre::eyre::Result;
+use common::data_types::{airport::AirFacility, airport_names::AIRPORT_NAMES};
+use itertools::Itertools;
+
+use crate::Action;
+
+pub fn nc(air_facilities: &[AirFacility]) -> Result<Action> {
+    let unnamed = AIRPORT_NAMES.unnamed(air_facilities);
+    let not_in_raw_data = AIRPORT_NAMES.not_in_raw_data(air_facilities);
+    Ok(Action::Msg(format!(
+        "{} air facility code(s) without a name: {}\n{} named airport(s) not in the raw data: {}",
+        unnamed.len(),
+        unnamed.iter().join(", "),
+        not_in_raw_data.len(),
+        not_in_raw_data.iter().join(", ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use crate::{cmds::test_setup, nc, Action};
+
+    #[test]
+    fn nc_normal() -> Result<()> {
+        let (air_facilities, _) = test_setup()?;
+        let Action::Msg(msg) = nc(air_facilities)? else {
+            panic!("Unsuccessful check");
+        };
+        assert!(
+            msg.contains("named airport(s) not in the raw data"),
+            "Faulty check: {msg}"
+        );
+        Ok(())
+    }
+}
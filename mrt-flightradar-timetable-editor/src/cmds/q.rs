@@ -1,20 +1,53 @@
-use color_eyre::eyre::Result;
+use std::{iter::Peekable, str::Split};
 
-use crate::cmds::Action;
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::timetable::AirlineTimetable;
 
-pub fn q() -> Result<Action> {
+use crate::cmds::{Action, State};
+
+pub fn q(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &AirlineTimetable,
+    state: &State,
+) -> Result<Action> {
+    if state.manual_save && cmd_str.peek() != Some(&"!") && state.unsaved(file) {
+        return Err(eyre!(
+            "There are unsaved changes, enter `w` to save or `q !` to quit without saving"
+        ));
+    }
     Ok(Action::Quit("Quitting".into()))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
     use color_eyre::eyre::Result;
 
-    use crate::{q, Action};
+    use crate::{
+        cmds::{test_setup, State},
+        q, to_cmd_str, Action,
+    };
 
     #[test]
     fn q_normal() -> Result<()> {
-        assert!(matches!(q().unwrap(), Action::Quit(_)), "Unsuccessful quit");
+        let (_, file) = test_setup()?;
+        let mut state = State::default();
+        assert!(
+            matches!(q(&mut to_cmd_str!(""), &file, &state)?, Action::Quit(_)),
+            "Unsuccessful quit"
+        );
+
+        state.manual_save = true;
+        state.path = PathBuf::from("nonexistent.fpln");
+        assert!(
+            q(&mut to_cmd_str!(""), &file, &state).is_err(),
+            "Quitting with unsaved changes did not error"
+        );
+        assert!(
+            matches!(q(&mut to_cmd_str!("!"), &file, &state)?, Action::Quit(_)),
+            "Unsuccessful forced quit"
+        );
         Ok(())
     }
 }
@@ -0,0 +1,124 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::timetable::AirlineTimetable;
+use regex::Regex;
+use smol_str::SmolStr;
+
+use crate::{
+    arg,
+    cmds::{confirm, get_number},
+    Action,
+};
+
+/// Adds `by` to the number at the end of a flight number, keeping its width
+fn increment_flight_no(flight_no: &str, by: usize) -> Result<SmolStr> {
+    let Some(caps) = Regex::new(r"^(.*?)(\d+)$")?.captures(flight_no) else {
+        return Err(eyre!(
+            "Flight number `{flight_no}` does not end in a number"
+        ));
+    };
+    let width = caps[2].len();
+    let number = caps[2].parse::<usize>()? + by;
+    Ok(format!("{}{number:0width$}", &caps[1]).into())
+}
+
+pub fn r(cmd_str: &mut Peekable<Split<char>>, file: &AirlineTimetable) -> Result<Action> {
+    let index = arg!(cmd_str "index" get_index, file, lt)?;
+    let count = get_number::<usize>(cmd_str, "count")?;
+    let interval = get_number::<i32>(cmd_str, "interval")?;
+    let flight = &file.flights[index];
+    let step = if cmd_str.peek().is_some() {
+        get_number::<usize>(cmd_str, "step")?
+    } else {
+        flight.segments.len()
+    };
+    if count == 0 {
+        return Err(eyre!("<count> must be at least 1"));
+    }
+
+    let mut flights = file.flights.to_owned();
+    for n in 1..=count {
+        let mut copy = flight.to_owned();
+        for seg in &mut copy.segments {
+            seg.flight_no = increment_flight_no(&seg.flight_no, n * step)?;
+        }
+        copy.shift(i32::try_from(n)? * interval);
+        flights.push(copy);
+    }
+    confirm(&file.flights, flights)
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use smol_str::SmolStr;
+
+    use crate::{cmds::test_setup, r, to_cmd_str, Action};
+
+    #[test]
+    fn r_normal() -> Result<()> {
+        let (_, file) = test_setup()?;
+        let mut cmd_str = to_cmd_str!("0 2 120");
+        let Action::Confirm(preview, flights) = r(&mut cmd_str, &file)? else {
+            panic!("Unsuccessful rotation");
+        };
+        assert!(preview.contains("2 flight(s)"), "Faulty preview");
+        assert_eq!(flights.len(), 3, "Faulty rotation");
+        assert_eq!(
+            flights[2].segments[0].flight_no,
+            SmolStr::from("AB127"),
+            "Faulty flight number"
+        );
+        assert_eq!(
+            flights[2].segments[1].depart_time.to_string(),
+            "1400",
+            "Faulty time"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn r_step() -> Result<()> {
+        let (_, file) = test_setup()?;
+        let mut cmd_str = to_cmd_str!("0 1 -60 10");
+        let Action::Confirm(_, flights) = r(&mut cmd_str, &file)? else {
+            panic!("Unsuccessful rotation");
+        };
+        assert_eq!(
+            flights[1].segments[1].flight_no,
+            SmolStr::from("CD466"),
+            "Faulty flight number"
+        );
+        assert_eq!(
+            flights[1].segments[0].depart_time.to_string(),
+            "2300",
+            "Faulty time"
+        );
+        Ok(())
+    }
+
+    macro_rules! assert_err {
+        ($fn_name:ident, $cmd:literal) => {
+            #[test]
+            fn $fn_name() -> Result<()> {
+                let (_, file) = test_setup()?;
+                let mut cmd_str = to_cmd_str!($cmd);
+                assert!(
+                    r(&mut cmd_str, &file).is_err(),
+                    "`{}` did not error",
+                    stringify!($fn_name)
+                );
+                Ok(())
+            }
+        };
+    }
+
+    assert_err!(r_no_index, "");
+    assert_err!(r_invalid_index, "1 2 60");
+    assert_err!(r_no_count, "0");
+    assert_err!(r_zero_count, "0 0 60");
+    assert_err!(r_no_interval, "0 2");
+    assert_err!(r_invalid_interval, "0 2 1h");
+    assert_err!(r_invalid_step, "0 2 60 x");
+}
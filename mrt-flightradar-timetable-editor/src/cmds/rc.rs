@@ -0,0 +1,62 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::Result;
+use common::data_types::{rotation::check_rotations, timetable::AirlineTimetable};
+use itertools::Itertools;
+
+use crate::{
+    cmds::{get_number, re::DEFAULT_TURNAROUND},
+    Action,
+};
+
+pub fn rc(cmd_str: &mut Peekable<Split<char>>, file: &AirlineTimetable) -> Result<Action> {
+    let min_turnaround = if cmd_str.peek().is_some_and(|arg| !arg.is_empty()) {
+        get_number::<i32>(cmd_str, "min_turnaround")?
+    } else {
+        DEFAULT_TURNAROUND
+    };
+    let issues = check_rotations(file, min_turnaround);
+    Ok(Action::Msg(if issues.is_empty() {
+        "No rotation issues".into()
+    } else {
+        format!(
+            "{} rotation issue(s):\n{}",
+            issues.len(),
+            issues.iter().join("\n")
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use crate::{cmds::test_setup, rc, to_cmd_str, Action};
+
+    #[test]
+    fn rc_normal() -> Result<()> {
+        let (_, mut file) = test_setup()?;
+        assert_eq!(
+            rc(&mut to_cmd_str!(""), &file)?,
+            Action::Msg("No rotation issues".into())
+        );
+        let mut other = file.flights[0].to_owned();
+        other.segments[0].airport = "KBN".into();
+        file.flights.push(other);
+        let Action::Msg(msg) = rc(&mut to_cmd_str!("30"), &file)? else {
+            panic!("Unsuccessful check");
+        };
+        assert!(
+            msg.starts_with("1 rotation issue(s):\n"),
+            "Faulty check: {msg}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rc_invalid_turnaround() -> Result<()> {
+        let (_, file) = test_setup()?;
+        assert!(rc(&mut to_cmd_str!("x"), &file).is_err());
+        Ok(())
+    }
+}
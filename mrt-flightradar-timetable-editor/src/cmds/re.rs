@@ -0,0 +1,152 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::{airport::AirFacility, timetable::AirlineTimetable};
+
+use crate::{
+    arg,
+    cmds::{confirm, e::estimate_time},
+    Action,
+};
+
+/// Minimum turnaround used when none is given, in minutes
+pub const DEFAULT_TURNAROUND: i32 = 15;
+
+pub fn re(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &AirlineTimetable,
+    air_facilities: &[AirFacility],
+) -> Result<Action> {
+    let index = arg!(cmd_str "index" get_index, file, lt)?;
+    let mut turnaround = DEFAULT_TURNAROUND;
+    let mut force = false;
+    for arg in cmd_str {
+        match (arg, arg.parse::<i32>()) {
+            ("!", _) => force = true,
+            (_, Ok(minutes)) if minutes >= 0 => turnaround = minutes,
+            _ => return Err(eyre!("Invalid turnaround `{arg}`")),
+        }
+    }
+
+    let mut flights = file.flights.to_owned();
+    let segments = &mut flights[index].segments;
+    for i in 1..segments.len() {
+        let (prev, seg) = (&segments[i - 1], &segments[i]);
+        let flight_time =
+            (estimate_time(&prev.airport, &seg.airport, air_facilities)?.0 * 60.0).round() as i32;
+        // arrivals are re-estimated too if the flight has them, and the turnaround counted from them
+        let arrive_time = seg.arrive_time.map(|arrive_time| {
+            if force || arrive_time.minutes_after(prev.depart_time) < flight_time {
+                prev.depart_time.add_minutes(flight_time)
+            } else {
+                arrive_time
+            }
+        });
+        let needed =
+            arrive_time.map_or(flight_time, |t| t.minutes_after(prev.depart_time)) + turnaround;
+        if force || seg.depart_time.minutes_after(prev.depart_time) < needed {
+            segments[i].depart_time = prev.depart_time.add_minutes(needed);
+        }
+        segments[i].arrive_time = arrive_time;
+    }
+    confirm(&file.flights, flights)
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::{eyre, Result};
+    use common::data_types::{airport::AirFacility, timetable::AirlineTimetable};
+
+    use crate::{
+        cmds::{re::DEFAULT_TURNAROUND, test_setup},
+        re, to_cmd_str, Action,
+    };
+
+    fn setup() -> Result<(&'static Vec<AirFacility>, AirlineTimetable)> {
+        let (air_facilities, mut file) = test_setup()?;
+        let segments = &mut file.flights[0].segments;
+        segments[0].airport = "PRA".into();
+        segments[1].airport = "KBN".into();
+        segments[1].depart_time = "0000".parse()?;
+        Ok((air_facilities, file))
+    }
+
+    #[test]
+    fn re_normal() -> Result<()> {
+        let (air_facilities, file) = setup()?;
+        let mut cmd_str = to_cmd_str!("0");
+        let Action::Confirm(_, flights) = re(&mut cmd_str, &file, air_facilities)? else {
+            panic!("Unsuccessful re-estimate");
+        };
+        assert_eq!(
+            flights[0].segments[1].depart_time.to_string(),
+            "1736",
+            "Faulty re-estimate"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn re_arrival() -> Result<()> {
+        let (air_facilities, mut file) = setup()?;
+        file.flights[0].segments[1].arrive_time = Some("0000".parse()?);
+        let mut cmd_str = to_cmd_str!("0");
+        let Action::Confirm(_, flights) = re(&mut cmd_str, &file, air_facilities)? else {
+            panic!("Unsuccessful re-estimate");
+        };
+        let seg = &flights[0].segments[1];
+        let arrive_time = seg
+            .arrive_time
+            .ok_or_else(|| eyre!("Arrival was removed"))?;
+        assert_ne!(arrive_time.to_string(), "0000", "Arrival not re-estimated");
+        assert_eq!(
+            seg.depart_time.minutes_after(arrive_time),
+            DEFAULT_TURNAROUND,
+            "Turnaround not counted from arrival"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn re_keeps_possible_times() -> Result<()> {
+        let (air_facilities, mut file) = setup()?;
+        file.flights[0].segments[1].depart_time = "2300".parse()?;
+        let mut cmd_str = to_cmd_str!("0 30");
+        assert!(
+            re(&mut cmd_str, &file, air_facilities).is_err(),
+            "Possible time was changed"
+        );
+
+        let mut cmd_str = to_cmd_str!("0 30 !");
+        let Action::Confirm(_, flights) = re(&mut cmd_str, &file, air_facilities)? else {
+            panic!("Unsuccessful re-estimate");
+        };
+        assert_ne!(
+            flights[0].segments[1].depart_time.to_string(),
+            "2300",
+            "Faulty forced re-estimate"
+        );
+        Ok(())
+    }
+
+    macro_rules! assert_err {
+        ($fn_name:ident, $cmd:literal) => {
+            #[test]
+            fn $fn_name() -> Result<()> {
+                let (air_facilities, file) = setup()?;
+                let mut cmd_str = to_cmd_str!($cmd);
+                assert!(
+                    re(&mut cmd_str, &file, air_facilities).is_err(),
+                    "`{}` did not error",
+                    stringify!($fn_name)
+                );
+                Ok(())
+            }
+        };
+    }
+
+    assert_err!(re_no_index, "");
+    assert_err!(re_invalid_index, "1");
+    assert_err!(re_invalid_turnaround, "0 x");
+    assert_err!(re_negative_turnaround, "0 -5");
+}
@@ -0,0 +1,67 @@
+use std::{fs, iter::Peekable, path::Path, str::Split};
+
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::{airport::AirFacility, timetable::AirlineTimetable};
+
+use crate::{cmds::get_path, report::Report, Action};
+
+pub fn st(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &AirlineTimetable,
+    file_path: &Path,
+    air_facilities: &[AirFacility],
+) -> Result<Action> {
+    let report = Report::new(file, air_facilities);
+    if cmd_str.peek().copied().unwrap_or_default().is_empty() {
+        return Ok(Action::Msg(report.to_string()));
+    }
+    let path = get_path(cmd_str, file_path)?;
+    if path.extension().is_none_or(|ext| ext != "json") {
+        return Err(eyre!("Statistics can only be exported to `.json` files"));
+    }
+    fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+    Ok(Action::Msg(format!(
+        "Exported statistics to {}",
+        path.display()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use color_eyre::eyre::Result;
+
+    use crate::{
+        cmds::{test_setup, TestDir},
+        st, to_cmd_str, Action,
+    };
+
+    #[test]
+    fn st_normal() -> Result<()> {
+        let (air_facilities, file) = test_setup()?;
+        let Action::Msg(msg) = st(
+            &mut to_cmd_str!(""),
+            &file,
+            "Test.fpln".as_ref(),
+            air_facilities,
+        )?
+        else {
+            panic!("Unsuccessful report");
+        };
+        assert!(msg.starts_with("Airports"), "Faulty report: {msg}");
+
+        let dir = TestDir::new()?;
+        let path = dir.join("Test.fpln");
+        st(&mut to_cmd_str!("stats.json"), &file, &path, air_facilities)?;
+        assert!(
+            fs::read_to_string(dir.join("stats.json"))?.contains(r#""routes": ["#),
+            "Faulty export"
+        );
+        assert!(
+            st(&mut to_cmd_str!("stats.txt"), &file, &path, air_facilities).is_err(),
+            "Unknown format did not error"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::timetable::{AirlineTimetable, Flight};
+
+use crate::Action;
+
+pub fn u(file: &mut AirlineTimetable, undo: &mut Vec<Vec<Flight>>) -> Result<Action> {
+    let Some(flights) = undo.pop() else {
+        return Err(eyre!("Nothing to undo"));
+    };
+    file.flights = flights;
+    Ok(Action::Refresh)
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use crate::{
+        cmds::{run, test_setup, State},
+        Action,
+    };
+
+    #[test]
+    fn u_normal() -> Result<()> {
+        let (air_facilities, mut file) = test_setup()?;
+        let original = file.to_owned();
+        let mut state = State::default();
+        run("d 0", &mut file, &mut state, air_facilities)?;
+        run("f reg REG", &mut file, &mut state, air_facilities)?;
+        run("u", &mut file, &mut state, air_facilities)?;
+        assert_eq!(file, original, "Faulty undo");
+        assert!(
+            run("u", &mut file, &mut state, air_facilities).is_err(),
+            "Undo with nothing to undo did not error"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn u_confirmed_change() -> Result<()> {
+        let (air_facilities, mut file) = test_setup()?;
+        let original = file.to_owned();
+        let mut state = State::default();
+        let Action::Confirm(_, flights) = run("r 0 5 60", &mut file, &mut state, air_facilities)?
+        else {
+            panic!("Unsuccessful rotation");
+        };
+        state.apply(&mut file, flights);
+        assert_eq!(file.flights.len(), 6, "Faulty rotation");
+        run("u", &mut file, &mut state, air_facilities)?;
+        assert_eq!(file, original, "Faulty undo");
+        Ok(())
+    }
+}
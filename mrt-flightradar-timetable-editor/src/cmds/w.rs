@@ -0,0 +1,32 @@
+use color_eyre::eyre::Result;
+use common::data_types::timetable::AirlineTimetable;
+
+use crate::cmds::{Action, State};
+
+pub fn w(file: &AirlineTimetable, state: &State) -> Result<Action> {
+    state.save(file)?;
+    Ok(Action::Msg(format!("Saved to {}", state.path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use crate::{
+        cmds::{test_setup, State, TestDir},
+        w,
+    };
+
+    #[test]
+    fn w_normal() -> Result<()> {
+        let (_, file) = test_setup()?;
+        let dir = TestDir::new()?;
+        let state = State {
+            path: dir.join("Test.fpln"),
+            ..State::default()
+        };
+        w(&file, &state)?;
+        assert!(!state.unsaved(&file), "Faulty save");
+        Ok(())
+    }
+}
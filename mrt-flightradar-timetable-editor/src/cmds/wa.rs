@@ -0,0 +1,39 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::{eyre, Result};
+
+use crate::cmds::{Action, State};
+
+pub fn wa(cmd_str: &mut Peekable<Split<char>>, state: &mut State) -> Result<Action> {
+    state.manual_save = match cmd_str.next() {
+        None | Some("") => !state.manual_save,
+        Some("on") => false,
+        Some("off") => true,
+        Some(a) => return Err(eyre!("Invalid option `{a}`, expected `on` or `off`")),
+    };
+    Ok(Action::Msg(if state.manual_save {
+        "Autosave is off, enter `w` to save".into()
+    } else {
+        "Autosave is on".into()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use crate::{cmds::State, to_cmd_str, wa};
+
+    #[test]
+    fn wa_normal() -> Result<()> {
+        let mut state = State::default();
+        wa(&mut to_cmd_str!(""), &mut state)?;
+        assert!(state.manual_save, "Faulty toggle");
+        wa(&mut to_cmd_str!("off"), &mut state)?;
+        assert!(state.manual_save, "Faulty autosave option");
+        wa(&mut to_cmd_str!("on"), &mut state)?;
+        assert!(!state.manual_save, "Faulty autosave option");
+        assert!(wa(&mut to_cmd_str!("maybe"), &mut state).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+use common::data_types::timetable::AirlineTimetable;
+use itertools::Itertools;
+
+use crate::{workspace::check, Action};
+
+pub fn wc(file: &AirlineTimetable, workspace: &[(AirlineTimetable, PathBuf)]) -> Result<Action> {
+    let airlines = std::iter::once(file)
+        .chain(workspace.iter().map(|(airline, _)| airline))
+        .collect::<Vec<_>>();
+    let issues = check(&airlines);
+    Ok(Action::Msg(if issues.is_empty() {
+        "No conflicts between airlines".into()
+    } else {
+        format!(
+            "{} conflict(s):\n{}",
+            issues.len(),
+            issues.iter().join("\n")
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use color_eyre::eyre::Result;
+
+    use crate::{cmds::test_setup, wc, Action};
+
+    #[test]
+    fn wc_normal() -> Result<()> {
+        let (_, file) = test_setup()?;
+        assert_eq!(
+            wc(&file, &[])?,
+            Action::Msg("No conflicts between airlines".into())
+        );
+        let mut other = file.to_owned();
+        other.name = "Other Air".into();
+        let Action::Msg(msg) = wc(&file, &[(other, PathBuf::new())])? else {
+            panic!("Unsuccessful check");
+        };
+        assert!(msg.starts_with("5 conflict(s):\n"), "Faulty check: {msg}");
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::Result;
+use common::data_types::timetable::AirlineTimetable;
+
+use crate::{
+    cmds::{wm::transfer, State},
+    Action,
+};
+
+pub fn wcp(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &mut AirlineTimetable,
+    state: &mut State,
+) -> Result<Action> {
+    transfer(cmd_str, file, state, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use color_eyre::eyre::Result;
+
+    use crate::{
+        cmds::{test_setup, State},
+        to_cmd_str, wcp,
+    };
+
+    #[test]
+    fn wcp_normal() -> Result<()> {
+        let (_, mut file) = test_setup()?;
+        let original = file.to_owned();
+        let mut state = State::default();
+        let mut other = file.to_owned();
+        other.name = "Other Air".into();
+        state.workspace.push((other, PathBuf::new()));
+
+        wcp(&mut to_cmd_str!("0 other air"), &mut file, &mut state)?;
+        assert_eq!(file, original, "Faulty copy");
+        let (other, _) = &state.workspace[0];
+        assert_eq!(other.flights.len(), 2, "Faulty copy");
+        assert_eq!(other.flights[1], original.flights[0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::Result;
+use common::data_types::timetable::AirlineTimetable;
+
+use crate::{
+    arg,
+    cmds::{get_airline, State},
+    Action,
+};
+
+pub fn wm(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &mut AirlineTimetable,
+    state: &mut State,
+) -> Result<Action> {
+    transfer(cmd_str, file, state, false)
+}
+
+/// Adds a flight to the end of another airline in the workspace, removing it from this one unless
+/// `copy` is set. Moving clears the undo history, as undoing would leave the flight in both airlines
+pub fn transfer(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &mut AirlineTimetable,
+    state: &mut State,
+    copy: bool,
+) -> Result<Action> {
+    let index = arg!(cmd_str "index" get_index, file, lt)?;
+    let airline = get_airline(cmd_str, &state.workspace)?;
+    let flight = if copy {
+        file.flights[index].to_owned()
+    } else {
+        state.undo.clear();
+        file.flights.remove(index)
+    };
+    let (airline, _) = &mut state.workspace[airline];
+    airline.flights.push(flight);
+    Ok(Action::Msg(format!(
+        "{} flight {index} to {} as flight {}",
+        if copy { "Copied" } else { "Moved" },
+        airline.name,
+        airline.flights.len() - 1
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use color_eyre::eyre::Result;
+    use common::data_types::timetable::AirlineTimetable;
+
+    use crate::{
+        cmds::{test_setup, State},
+        to_cmd_str, wm,
+    };
+
+    fn setup() -> Result<(AirlineTimetable, State)> {
+        let (_, file) = test_setup()?;
+        let mut state = State::default();
+        let mut other = file.to_owned();
+        other.name = "Other Air".into();
+        other.flights.clear();
+        state.workspace.push((other, PathBuf::new()));
+        Ok((file, state))
+    }
+
+    #[test]
+    fn wm_normal() -> Result<()> {
+        let (mut file, mut state) = setup()?;
+        let flight = file.flights[0].to_owned();
+        wm(&mut to_cmd_str!("0 Other Air"), &mut file, &mut state)?;
+        assert!(file.flights.is_empty(), "Faulty move");
+        assert_eq!(state.workspace[0].0.flights, [flight], "Faulty move");
+        Ok(())
+    }
+
+    macro_rules! assert_err {
+        ($fn_name:ident, $cmd:literal) => {
+            #[test]
+            fn $fn_name() -> Result<()> {
+                let (mut file, mut state) = setup()?;
+                let mut cmd_str = to_cmd_str!($cmd);
+                assert!(
+                    wm(&mut cmd_str, &mut file, &mut state).is_err(),
+                    "`{}` did not error",
+                    stringify!($fn_name)
+                );
+                Ok(())
+            }
+        };
+    }
+
+    assert_err!(wm_no_index, "");
+    assert_err!(wm_invalid_index, "1 Other Air");
+    assert_err!(wm_no_airline, "0");
+    assert_err!(wm_unknown_airline, "0 Nonexistent Air");
+}
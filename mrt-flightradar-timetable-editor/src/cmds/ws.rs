@@ -0,0 +1,127 @@
+use std::{iter::Peekable, str::Split};
+
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::{fleet::Fleet, timetable::AirlineTimetable};
+use itertools::Itertools;
+
+use crate::{
+    cmds::{get_airline, State},
+    filter::Filter,
+    Action,
+};
+
+pub fn ws(
+    cmd_str: &mut Peekable<Split<char>>,
+    file: &mut AirlineTimetable,
+    state: &mut State,
+) -> Result<Action> {
+    if state.workspace.is_empty() {
+        return Err(eyre!(
+            "No other airlines are open, start the editor with `--workspace` to open a directory"
+        ));
+    }
+    if cmd_str.peek().copied().unwrap_or_default().is_empty() {
+        let list = std::iter::once(&*file)
+            .chain(state.workspace.iter().map(|(airline, _)| airline))
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .map(|airline| {
+                format!(
+                    "{} {}\t{} flight(s)",
+                    if airline.name == file.name { "*" } else { " " },
+                    airline.name,
+                    airline.flights.len()
+                )
+            })
+            .join("\n");
+        return Ok(Action::Msg(list));
+    }
+    let index = get_airline(cmd_str, &state.workspace)?;
+    let (airline, path) = &mut state.workspace[index];
+    let fleet = Fleet::from_timetable_path(path)?;
+    std::mem::swap(file, airline);
+    std::mem::swap(&mut state.path, path);
+    state
+        .workspace
+        .sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+    state.filter = Filter::default();
+    state.undo.clear();
+    state.fleet = fleet;
+    Ok(Action::Refresh)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use color_eyre::eyre::Result;
+
+    use crate::{
+        cmds::{test_setup, State, TestDir},
+        to_cmd_str, ws, Action,
+    };
+
+    #[test]
+    fn ws_normal() -> Result<()> {
+        let (_, mut file) = test_setup()?;
+        let mut state = State::default();
+        let mut other = file.to_owned();
+        other.name = "Other Air".into();
+        other.flights.clear();
+        state.workspace.push((other, PathBuf::new()));
+
+        let Action::Msg(list) = ws(&mut to_cmd_str!(""), &mut file, &mut state)? else {
+            panic!("Unsuccessful listing");
+        };
+        assert_eq!(list, "  Other Air\t0 flight(s)\n* Test\t1 flight(s)");
+        assert_eq!(
+            ws(&mut to_cmd_str!("other air"), &mut file, &mut state)?,
+            Action::Refresh,
+            "Unsuccessful switch"
+        );
+        assert_eq!(file.name, "Other Air", "Faulty switch");
+        assert_eq!(state.workspace[0].0.name, "Test", "Faulty switch");
+        Ok(())
+    }
+
+    #[test]
+    fn ws_invalid_fleet() -> Result<()> {
+        let dir = TestDir::new()?;
+        fs::write(dir.join("other.fleet"), "no tab here")?;
+        let (_, mut file) = test_setup()?;
+        let mut state = State::default();
+        let mut other = file.to_owned();
+        other.name = "Other Air".into();
+        state.workspace.push((other, dir.join("other.fpln")));
+
+        assert!(
+            ws(&mut to_cmd_str!("other air"), &mut file, &mut state).is_err(),
+            "Invalid fleet did not error"
+        );
+        assert_eq!(file.name, "Test", "Switched despite the invalid fleet");
+        assert_eq!(state.workspace[0].1, dir.join("other.fpln"));
+        Ok(())
+    }
+
+    macro_rules! assert_err {
+        ($fn_name:ident, $cmd:literal) => {
+            #[test]
+            fn $fn_name() -> Result<()> {
+                let (_, mut file) = test_setup()?;
+                let mut state = State::default();
+                let mut other = file.to_owned();
+                other.name = "Other Air".into();
+                state.workspace.push((other, PathBuf::new()));
+                let mut cmd_str = to_cmd_str!($cmd);
+                assert!(
+                    ws(&mut cmd_str, &mut file, &mut state).is_err(),
+                    "`{}` did not error",
+                    stringify!($fn_name)
+                );
+                Ok(())
+            }
+        };
+    }
+
+    assert_err!(ws_unknown_airline, "Nonexistent Air");
+    assert_err!(ws_current_airline, "Test");
+}
@@ -0,0 +1,105 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::NaiveDate;
+use color_eyre::eyre::Result;
+use common::data_types::{
+    time::Time,
+    timetable::{AirportCode, Flight, DATE_FORMAT},
+};
+use itertools::Itertools;
+use regex::{Regex, RegexBuilder};
+use smol_str::SmolStr;
+
+#[derive(Clone, Debug)]
+pub enum Criterion {
+    /// Aircraft name contains the string, case-insensitively
+    Aircraft(SmolStr),
+    Registry(SmolStr),
+    /// Any segment is at the airport
+    Airport(AirportCode),
+    /// Any flight number matches the pattern, where `*` matches any characters and `?` one
+    FlightNo(SmolStr, Regex),
+    /// Any segment departs within the window, whatever the day, which may wrap past midnight
+    Departure(Time, Time),
+    OperatesOn(NaiveDate),
+}
+
+impl Criterion {
+    pub fn flight_no(pattern: &str) -> Result<Self> {
+        let re = regex::escape(pattern)
+            .replace(r"\*", ".*")
+            .replace(r"\?", ".");
+        Ok(Self::FlightNo(
+            pattern.into(),
+            RegexBuilder::new(&format!("^{re}$"))
+                .case_insensitive(true)
+                .build()?,
+        ))
+    }
+
+    #[must_use]
+    pub fn matches(&self, flight: &Flight) -> bool {
+        match self {
+            Self::Aircraft(aircraft) => flight
+                .aircraft
+                .to_lowercase()
+                .contains(&aircraft.to_lowercase()),
+            Self::Registry(registry) => flight.registry.eq_ignore_ascii_case(registry),
+            Self::Airport(airport) => flight.segments.iter().any(|seg| seg.airport == *airport),
+            Self::FlightNo(_, re) => flight
+                .segments
+                .iter()
+                .any(|seg| re.is_match(&seg.flight_no)),
+            Self::Departure(from, to) => flight.segments.iter().any(|seg| {
+                let time = seg.depart_time.time_of_day();
+                if from <= to {
+                    (from..=to).contains(&&time)
+                } else {
+                    time >= *from || time <= *to
+                }
+            }),
+            Self::OperatesOn(date) => flight.operates_on(*date),
+        }
+    }
+}
+
+impl Display for Criterion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Aircraft(aircraft) => write!(f, "aircraft ~ \"{aircraft}\""),
+            Self::Registry(registry) => write!(f, "registry = {registry}"),
+            Self::Airport(airport) => write!(f, "via {airport}"),
+            Self::FlightNo(pattern, _) => write!(f, "flight no. {pattern}"),
+            Self::Departure(from, to) => write!(f, "departing {from}-{to}"),
+            Self::OperatesOn(date) => write!(f, "operating on {}", date.format(DATE_FORMAT)),
+        }
+    }
+}
+
+/// Flights shown in the editor. Flights keep their index in the timetable, so commands taking
+/// an `<index>` work the same while filtered
+#[derive(Clone, Debug, Default)]
+pub struct Filter(pub Vec<Criterion>);
+
+impl Filter {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[must_use]
+    pub fn matches(&self, flight: &Flight) -> bool {
+        self.0.iter().all(|c| c.matches(flight))
+    }
+
+    /// Matching flights along with their index in the timetable
+    pub fn apply<'a>(&'a self, flights: &'a [Flight]) -> impl Iterator<Item = (usize, &'a Flight)> {
+        flights.iter().enumerate().filter(|(_, f)| self.matches(f))
+    }
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.iter().join(", "))
+    }
+}
@@ -0,0 +1,242 @@
+use std::borrow::Cow;
+
+use common::data_types::{
+    airport::AirFacility, airport_names::AIRPORT_NAMES, timetable::AirportCode,
+};
+use rustyline::{
+    completion::Completer, highlight::Highlighter, hint::Hinter, validate::Validator, Context,
+    Helper,
+};
+
+use crate::cmds::h::CMDS;
+
+const SEGMENT_ARGS: [&str; 3] = ["<flight_no>", "<airport>", "<depart_time>"];
+
+/// Completes and hints commands in the REPL, using the argument lists of the help page
+pub struct EditorHelper {
+    airports: Vec<AirportCode>,
+    /// Number of segments of each flight in the timetable, updated before every prompt
+    pub flights: Vec<usize>,
+}
+
+impl EditorHelper {
+    #[must_use]
+    pub fn new(air_facilities: &[AirFacility]) -> Self {
+        let mut airports = air_facilities
+            .iter()
+            .map(|af| af.code().to_owned())
+            .chain(AIRPORT_NAMES.codes().cloned())
+            .collect::<Vec<_>>();
+        airports.sort_unstable();
+        airports.dedup();
+        Self {
+            airports,
+            flights: vec![],
+        }
+    }
+
+    fn candidates(&self, args: &[&str], word: &str) -> Vec<String> {
+        let Some(cmd) = args.first() else {
+            return CMDS
+                .iter()
+                .map(|(cmd, ..)| *cmd)
+                .filter(|cmd| cmd.starts_with(word))
+                .map(Into::into)
+                .collect();
+        };
+        let segments = || {
+            args.get(1)
+                .and_then(|i| i.parse::<usize>().ok())
+                .and_then(|i| self.flights.get(i))
+                .copied()
+                .unwrap_or_default()
+        };
+        let candidates = match arg_name(cmd, args.len() - 1) {
+            Some("<index>" | "<index1>" | "<index2>") => {
+                (0..self.flights.len()).map(|i| i.to_string()).collect()
+            }
+            Some("<segment_index>") => (0..segments()).map(|i| i.to_string()).collect(),
+            Some("<field>") => {
+                let fields =
+                    (1..=segments()).flat_map(|n| ["f", "a", "d", "r"].map(|f| format!("{f}{n}")));
+                [
+                    "a".into(),
+                    "reg".into(),
+                    "days".into(),
+                    "from".into(),
+                    "to".into(),
+                ]
+                .into_iter()
+                .chain(fields)
+                .collect()
+            }
+            Some("<filter>") => ["a", "reg", "apt", "f", "d", "on"].map(Into::into).into(),
+            Some("<airport>" | "<a1>" | "<a2>" | "<old_airport>" | "<new_airport>") => {
+                let word = word.to_uppercase();
+                return self
+                    .airports
+                    .iter()
+                    .filter(|code| code.starts_with(&word))
+                    .map(ToString::to_string)
+                    .collect();
+            }
+            _ => vec![],
+        };
+        candidates
+            .into_iter()
+            .filter(|c: &String| c.starts_with(word))
+            .collect()
+    }
+}
+
+/// Splits a command into its arguments, keeping a quoted aircraft name as one argument.
+/// Also returns whether the line ends inside quotes
+fn split_args(line: &str) -> (Vec<&str>, bool) {
+    let mut args = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ' ' if !quoted => {
+                args.extend(line.get(start..i).filter(|arg| !arg.is_empty()));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    args.extend(line.get(start..).filter(|arg| !arg.is_empty()));
+    (args, quoted)
+}
+
+/// Name of the `n`th argument of `cmd` as shown on the help page, with segments expanded
+fn arg_name(cmd: &str, n: usize) -> Option<&'static str> {
+    let (_, args, _) = CMDS.iter().find(|(c, ..)| *c == cmd)?;
+    for (i, arg) in args.split_whitespace().enumerate() {
+        match arg {
+            "<segment>" if n - i < SEGMENT_ARGS.len() => return Some(SEGMENT_ARGS[n - i]),
+            "<segments>" => return Some(SEGMENT_ARGS[(n - i) % SEGMENT_ARGS.len()]),
+            "<segment>" => return None,
+            arg if i == n => return Some(arg),
+            _ => {}
+        }
+    }
+    None
+}
+
+impl Completer for EditorHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = line.get(..pos).unwrap_or(line);
+        let (line, word) = line.rsplit_once(' ').unwrap_or(("", line));
+        let start = pos - word.len();
+        let (args, quoted) = split_args(line);
+        if quoted {
+            return Ok((start, vec![]));
+        }
+        Ok((start, self.candidates(&args, word)))
+    }
+}
+
+impl Hinter for EditorHelper {
+    type Hint = String;
+
+    fn hint(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Option<String> {
+        if pos < line.len() {
+            return None;
+        }
+        let (args, quoted) = split_args(line);
+        let cmd = args.first()?;
+        if quoted {
+            None
+        } else if line.ends_with(' ') {
+            arg_name(cmd, args.len() - 1).map(Into::into)
+        } else if args.len() == 1 {
+            arg_name(cmd, 0).map(|arg| format!(" {arg}"))
+        } else {
+            None
+        }
+    }
+}
+
+impl Highlighter for EditorHelper {
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        Cow::Owned(format!("\x1B[2m{hint}\x1B[m"))
+    }
+}
+
+impl Validator for EditorHelper {}
+
+impl Helper for EditorHelper {}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use rustyline::{completion::Completer, hint::Hinter, history::DefaultHistory, Context};
+
+    use crate::{cmds::test_setup, helper::EditorHelper};
+
+    fn helper() -> Result<EditorHelper> {
+        let (air_facilities, file) = test_setup()?;
+        let mut helper = EditorHelper::new(air_facilities);
+        helper.flights = file.flights.iter().map(|f| f.segments.len()).collect();
+        Ok(helper)
+    }
+
+    fn complete(helper: &EditorHelper, line: &str) -> Result<Vec<String>> {
+        Ok(helper
+            .complete(line, line.len(), &Context::new(&DefaultHistory::new()))?
+            .1)
+    }
+
+    fn hint(helper: &EditorHelper, line: &str) -> Option<String> {
+        helper.hint(line, line.len(), &Context::new(&DefaultHistory::new()))
+    }
+
+    #[test]
+    fn complete_commands() -> Result<()> {
+        let helper = helper()?;
+        assert_eq!(complete(&helper, "s")?, ["st", "sa", "sae", "sas", "sd"]);
+        assert_eq!(complete(&helper, "d ")?, ["0"]);
+        assert_eq!(
+            complete(&helper, "c 0 ")?,
+            ["a", "reg", "days", "from", "to", "f1", "a1", "d1", "r1", "f2", "a2", "d2", "r2"]
+        );
+        assert_eq!(complete(&helper, "c 0 d")?, ["days", "d1", "d2"]);
+        Ok(())
+    }
+
+    #[test]
+    fn complete_airports() -> Result<()> {
+        let helper = helper()?;
+        let airports = complete(&helper, r#"ie "Test Aircraft" REG AB123 pr"#)?;
+        assert!(!airports.is_empty());
+        assert!(airports.iter().all(|a| a.starts_with("PR")));
+        assert!(complete(&helper, r#"ie "Test Air"#)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn hints() -> Result<()> {
+        let helper = helper()?;
+        assert_eq!(hint(&helper, "d").as_deref(), Some(" <index>"));
+        assert_eq!(hint(&helper, "sae 0 ").as_deref(), Some("<flight_no>"));
+        assert_eq!(
+            hint(&helper, "sae 0 AB1 PRA ").as_deref(),
+            Some("<depart_time>")
+        );
+        assert_eq!(
+            hint(&helper, r#"is "Test Aircraft" REG AB1 PRA 0000 "#).as_deref(),
+            Some("<flight_no>")
+        );
+        assert_eq!(hint(&helper, "sae 0 AB1 PRA 0000 "), None);
+        assert_eq!(hint(&helper, "x "), None);
+        Ok(())
+    }
+}
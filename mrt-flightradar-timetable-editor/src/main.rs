@@ -137,21 +137,33 @@
     clippy::wildcard_dependencies
 )]
 
-mod airport_names;
 mod cmds;
+mod filter;
+mod helper;
+mod report;
+mod save;
+mod tui;
+mod workspace;
 
 use std::path::Path;
 
 use bunt::println;
 use color_eyre::eyre::{eyre, Result};
-use common::data_types::{timetable::AirlineTimetable, RAW_DATA};
+use common::data_types::{rotation::check_rotations, timetable::AirlineTimetable, RAW_DATA};
 use itertools::Itertools;
 use native_dialog::FileDialog;
 use rustyline::{error::ReadlineError, history::FileHistory, Editor};
 
+#[cfg(test)]
 use crate::cmds::{
-    c::c, d::d, e::e, h::h, i::i, ie::ie, is::is, m::m, n::n, q::q, sa::sa, sae::sae, sas::sas,
-    sd::sd, Action,
+    ba::ba, bap::bap, bf::bf, bs::bs, c::c, d::d, e::e, export::export, f::f, fc::fc, h::h, i::i,
+    ie::ie, import::import, is::is, m::m, n::n, nc::nc, q::q, r::r, rc::rc, re::re, sa::sa,
+    sae::sae, sas::sas, sd::sd, st::st, w::w, wa::wa, wc::wc, wcp::wcp, wm::wm, ws::ws,
+};
+use crate::{
+    cmds::{duration, re::DEFAULT_TURNAROUND, run, schedule, Action, State},
+    helper::EditorHelper,
+    workspace::load_dir,
 };
 
 macro_rules! cprintln {
@@ -163,78 +175,160 @@ macro_rules! cprintln {
     }
 }
 
+/// Checks the rotations of timetable files, or of every timetable in directories, without
+/// opening the editor. Errors if there are any issues
+fn check(paths: &[String]) -> Result<()> {
+    if paths.is_empty() {
+        return Err(eyre!("No timetable files or directories to check"));
+    }
+    let mut count = 0;
+    for path in paths.iter().map(Path::new) {
+        let airlines = if path.is_dir() {
+            load_dir(path)?
+        } else {
+            vec![(
+                AirlineTimetable::from_file(path.to_owned())?,
+                path.to_owned(),
+            )]
+        };
+        for (airline, path) in airlines {
+            let issues = check_rotations(&airline, DEFAULT_TURNAROUND);
+            println!("{}: {} rotation issue(s)", path.display(), issues.len());
+            for issue in &issues {
+                cprintln!(red "  {issue}");
+            }
+            count += issues.len();
+        }
+    }
+    if count == 0 {
+        Ok(())
+    } else {
+        Err(eyre!("{count} rotation issue(s) found"))
+    }
+}
+
 fn main() -> Result<()> {
-    let mut rl = Editor::<(), FileHistory>::new()?;
+    let args = std::env::args().collect::<Vec<_>>();
+    if let Some(i) = args.iter().position(|arg| arg == "--check") {
+        return check(args.get(i + 1..).unwrap_or_default());
+    }
+    let mut rl = Editor::<EditorHelper, FileHistory>::new()?;
     cprintln!(yellow "MRT FlightRadar Timetable Editor");
-    let (mut file, path) = loop {
+    let workspace = std::env::args().any(|arg| arg == "--workspace");
+    let (mut file, path, others) = loop {
+        if workspace {
+            println!("Select directory...");
+            let Some(dir) = FileDialog::new().show_open_single_dir()? else {
+                cprintln!(yellow "Quitting");
+                return Ok(());
+            };
+            let mut airlines = match load_dir(&dir) {
+                Ok(airlines) if airlines.is_empty() => {
+                    cprintln!(red "No timetable files in directory");
+                    continue;
+                }
+                Ok(airlines) => airlines,
+                Err(err) => {
+                    cprintln!(red "Error reading directory: {err}");
+                    continue;
+                }
+            };
+            let (file, path) = airlines.remove(0);
+            break (file, path, airlines);
+        }
         println!("Select file...");
         let dialog = FileDialog::new()
             .add_filter("MRT FlightRadar timetable file", &["fpln"])
             .show_open_single_file()?;
-        let Some(file) = dialog else {
+        let Some(path) = dialog else {
             cprintln!(yellow "Quitting");
             return Ok(());
         };
         break (
-            match AirlineTimetable::from_file(file.to_owned()) {
+            match AirlineTimetable::from_file(path.to_owned()) {
                 Ok(at) => at,
                 Err(err) => {
                     cprintln!(red "Error reading file: {err}");
                     continue;
                 }
             },
-            file.parent().map_or(file.to_owned(), Path::to_path_buf),
+            path,
+            vec![],
         );
     };
 
-    let air_facilities = &RAW_DATA.air_facilities;
+    let mut state = State::default();
+    state.path = path;
+    state.workspace = others;
+    state.manual_save = std::env::args().any(|arg| arg == "--no-autosave");
+    if let Err(err) = state.load_fleet() {
+        cprintln!(red "Error reading fleet file, continuing without it: {err}");
+    }
+    let raw_data = RAW_DATA.get();
+    let air_facilities = &raw_data.air_facilities;
+    if std::env::args().any(|arg| arg == "--tui") {
+        tui::run(&mut file, state, air_facilities)?;
+        cprintln!(yellow "Quitting");
+        return Ok(());
+    }
+    rl.set_helper(Some(EditorHelper::new(air_facilities)));
     loop {
         print!("\x1B[2J\x1B[1;1H");
-        println!("Editing {[yellow]}\nEnter {$cyan}h{/$} for help", file.name);
-        cprintln!(yellow "#\t(a) Aircraft\t(reg) Registry\t(f1) Flight 1\t(a1) Airport 1\t(d1) Dep. 1\t(f2) Flight 2\t\t(a2) Airport 2\t(d2) Dep. 2\tetc...");
+        println!(
+            "Editing {[yellow]}{}\nEnter {$cyan}h{/$} for help",
+            file.name,
+            if state.manual_save && state.unsaved(&file) {
+                " (unsaved, enter w to save)"
+            } else {
+                ""
+            }
+        );
+        cprintln!(yellow "#\t(a) Aircraft\t(reg) Registry\t(days) Days\t(f1) Flight 1\t(a1) Airport 1\t(d1) Dep. 1\tBlock\t(f2) Flight 2\t\t(a2) Airport 2\t(d2) Dep. 2\tetc...");
+        for warning in state.fleet.check(&file.flights) {
+            cprintln!(red "Warning: {warning}");
+        }
+        let filter = &state.filter;
+        if !filter.is_empty() {
+            println!(
+                "Filter: {[cyan]} ({} of {} flights, {$cyan}fc{/$} to clear)",
+                filter,
+                filter.apply(&file.flights).count(),
+                file.flights.len()
+            );
+        }
         println!(
             "{}",
-            file.flights
-                .iter()
-                .enumerate()
+            filter
+                .apply(&file.flights)
                 .map(|(i, f)| format!(
-                    "{}\t{}\t\t{}\t\t{}",
+                    "{}\t{}\t\t{}\t\t{}\t{}",
                     i,
                     f.aircraft,
                     f.registry,
+                    schedule(f),
                     f.segments
                         .iter()
-                        .map(|seg| format!(
-                            "{}\t\t{}\t\t{}",
-                            seg.flight_no, seg.airport, seg.depart_time
+                        .enumerate()
+                        .map(|(n, seg)| format!(
+                            "{}\t\t{}\t\t{}{}{}",
+                            seg.flight_no,
+                            seg.airport,
+                            seg.arrive_time.map(|t| format!("{t}-")).unwrap_or_default(),
+                            seg.depart_time,
+                            f.block_time(n)
+                                .map(|b| format!("\t{}", duration(b)))
+                                .unwrap_or_default()
                         ))
                         .join("\t\t")
                 ))
                 .join("\n")
         );
+        if let Some(helper) = rl.helper_mut() {
+            helper.flights = file.flights.iter().map(|f| f.segments.len()).collect();
+        }
         match rl.readline("> ") {
             Ok(cmd_str) => {
-                let mut cmd_str = cmd_str.split(' ').peekable();
-
-                let action = match cmd_str.next() {
-                    Some("q") => q(),
-                    Some("h") => h(),
-                    Some("i") => i(&mut cmd_str, &mut file, air_facilities),
-                    Some("is") => is(&mut cmd_str, &mut file, air_facilities),
-                    Some("ie") => ie(&mut cmd_str, &mut file, air_facilities),
-                    Some("c") => c(&mut cmd_str, &mut file),
-                    Some("d") => d(&mut cmd_str, &mut file),
-                    Some("m") => m(&mut cmd_str, &mut file),
-                    Some("e") => e(&mut cmd_str, air_facilities),
-                    Some("n") => n(&mut cmd_str),
-                    Some("sa") => sa(&mut cmd_str, &mut file, air_facilities),
-                    Some("sae") => sae(&mut cmd_str, &mut file, air_facilities),
-                    Some("sas") => sas(&mut cmd_str, &mut file, air_facilities),
-                    Some("sd") => sd(&mut cmd_str, &mut file),
-                    Some(a) => Err(eyre!("Unknown command `{a}`")),
-                    None => Ok(Action::Refresh),
-                };
-                match action {
+                match run(&cmd_str, &mut file, &mut state, air_facilities) {
                     Ok(Action::Refresh) => {}
                     Ok(Action::Hold) => {
                         let _ = rl.readline("Press enter to continue...");
@@ -243,9 +337,18 @@ fn main() -> Result<()> {
                         cprintln!(yellow "{str}");
                         let _ = rl.readline("Press enter to continue...");
                     }
+                    Ok(Action::Confirm(preview, flights)) => {
+                        cprintln!(yellow "{preview}");
+                        if rl
+                            .readline("Apply? [y/N] ")
+                            .is_ok_and(|a| a.trim().eq_ignore_ascii_case("y"))
+                        {
+                            state.apply(&mut file, flights);
+                        }
+                    }
                     Ok(Action::Quit(str)) => {
                         cprintln!(yellow "{str}");
-                        file.to_file(path)?;
+                        state.autosave(&file)?;
                         return Ok(());
                     }
                     Err(err) => {
@@ -254,10 +357,13 @@ fn main() -> Result<()> {
                     }
                 }
 
-                file.to_file(path.to_owned())?;
+                state.autosave(&file)?;
             }
             Err(ReadlineError::Interrupted | ReadlineError::Eof) => {
-                file.to_file(path)?;
+                state.autosave(&file)?;
+                if state.unsaved(&file) {
+                    cprintln!(red "Unsaved changes were discarded");
+                }
                 cprintln!(yellow "Quitting");
                 return Ok(());
             }
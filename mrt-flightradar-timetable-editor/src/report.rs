@@ -0,0 +1,234 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+};
+
+use chrono::Duration;
+use common::data_types::{
+    airport::AirFacility,
+    airport_names::AIRPORT_NAMES,
+    timetable::{AirlineTimetable, AirportCode},
+};
+use itertools::Itertools;
+use serde::Serialize;
+use smol_str::SmolStr;
+
+use crate::cmds::e::estimate_time;
+
+/// Number of rows of the longer tables that are printed. Every row is exported
+const TABLE_ROWS: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AirportStats {
+    pub code: AirportCode,
+    pub name: Option<SmolStr>,
+    pub departures: u32,
+    pub arrivals: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HourStats {
+    pub hour: i64,
+    pub departures: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RouteStats {
+    pub from: AirportCode,
+    pub to: AirportCode,
+    pub flights: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RegistryStats {
+    pub registry: SmolStr,
+    pub aircraft: SmolStr,
+    pub legs: u32,
+    /// From the scheduled departure of each leg to its arrival at the next airport
+    pub block_hours: f32,
+    /// From [`estimate_time`], leaving out legs between airports it can't estimate
+    pub estimated_hours: f32,
+}
+
+/// Statistics of a timetable. Counts are per week, taking the days each flight operates on into
+/// account, and a leg is a flight between two consecutive segments
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Report {
+    /// Airports with the most departures and arrivals first
+    pub airports: Vec<AirportStats>,
+    /// Hours of the day with the most departures first, in the local time of the timetable
+    pub busiest_hours: Vec<HourStats>,
+    /// Most frequent routes first
+    pub routes: Vec<RouteStats>,
+    /// Registries with the most block hours first
+    pub registries: Vec<RegistryStats>,
+    /// Airports in the airport name list without any flights
+    pub unserved_airports: Vec<AirportCode>,
+}
+
+impl Report {
+    #[must_use]
+    pub fn new(file: &AirlineTimetable, air_facilities: &[AirFacility]) -> Self {
+        let mut airports = BTreeMap::<&AirportCode, (u32, u32)>::new();
+        let mut hours = BTreeMap::<i64, u32>::new();
+        let mut routes = BTreeMap::<(&AirportCode, &AirportCode), u32>::new();
+        let mut registries = BTreeMap::<&SmolStr, RegistryStats>::new();
+        let mut estimates = BTreeMap::<(&AirportCode, &AirportCode), Option<f32>>::new();
+
+        for flight in &file.flights {
+            let days = flight.days.count();
+            let registry = registries
+                .entry(&flight.registry)
+                .or_insert_with(|| RegistryStats {
+                    registry: flight.registry.to_owned(),
+                    aircraft: flight.aircraft.to_owned(),
+                    legs: 0,
+                    block_hours: 0.0,
+                    estimated_hours: 0.0,
+                });
+            for (leg, (seg, next)) in flight.segments.iter().tuple_windows().enumerate() {
+                airports.entry(&seg.airport).or_default().0 += days;
+                airports.entry(&next.airport).or_default().1 += days;
+                let hour = Duration::from(seg.depart_time.time_of_day()).num_hours();
+                *hours.entry(hour).or_default() += days;
+                *routes.entry((&seg.airport, &next.airport)).or_default() += days;
+
+                let estimate = *estimates
+                    .entry((&seg.airport, &next.airport))
+                    .or_insert_with(|| {
+                        estimate_time(&seg.airport, &next.airport, air_facilities)
+                            .ok()
+                            .map(|(hours, _)| hours)
+                    });
+                registry.legs += days;
+                registry.block_hours +=
+                    flight.block_time(leg).unwrap_or_default() as f32 / 60.0 * days as f32;
+                registry.estimated_hours += estimate.unwrap_or_default() * days as f32;
+            }
+        }
+
+        Self {
+            unserved_airports: AIRPORT_NAMES
+                .codes()
+                .filter(|code| !airports.contains_key(code))
+                .cloned()
+                .collect(),
+            airports: airports
+                .into_iter()
+                .map(|(code, (departures, arrivals))| AirportStats {
+                    code: code.to_owned(),
+                    name: AIRPORT_NAMES.name(code).cloned(),
+                    departures,
+                    arrivals,
+                })
+                .sorted_by_key(|a| std::cmp::Reverse(a.departures + a.arrivals))
+                .collect(),
+            busiest_hours: hours
+                .into_iter()
+                .map(|(hour, departures)| HourStats { hour, departures })
+                .sorted_by_key(|h| std::cmp::Reverse(h.departures))
+                .collect(),
+            routes: routes
+                .into_iter()
+                .map(|((from, to), flights)| RouteStats {
+                    from: from.to_owned(),
+                    to: to.to_owned(),
+                    flights,
+                })
+                .sorted_by_key(|r| std::cmp::Reverse(r.flights))
+                .collect(),
+            registries: registries
+                .into_values()
+                .sorted_by(|a, b| b.block_hours.total_cmp(&a.block_hours))
+                .collect(),
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let more = |len: usize| {
+            if len > TABLE_ROWS {
+                format!("\n... and {} more", len - TABLE_ROWS)
+            } else {
+                String::new()
+            }
+        };
+        writeln!(f, "Airports (flights per week)\nCode\tDep.\tArr.\tName")?;
+        for airport in self.airports.iter().take(TABLE_ROWS) {
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}",
+                airport.code,
+                airport.departures,
+                airport.arrivals,
+                airport.name.as_deref().unwrap_or_default()
+            )?;
+        }
+        writeln!(f, "{}", more(self.airports.len()))?;
+
+        writeln!(f, "Busiest hours (departures per week)\nHour\tDep.")?;
+        for hour in self.busiest_hours.iter().take(TABLE_ROWS) {
+            writeln!(f, "{:02}00\t{}", hour.hour, hour.departures)?;
+        }
+        writeln!(f, "{}", more(self.busiest_hours.len()))?;
+
+        writeln!(f, "Routes (flights per week)\nFrom\tTo\tFlights")?;
+        for route in self.routes.iter().take(TABLE_ROWS) {
+            writeln!(f, "{}\t{}\t{}", route.from, route.to, route.flights)?;
+        }
+        writeln!(f, "{}", more(self.routes.len()))?;
+
+        writeln!(
+            f,
+            "Aircraft utilisation (per week)\nReg.\tLegs\tBlock\tEst.\tAircraft"
+        )?;
+        for registry in &self.registries {
+            writeln!(
+                f,
+                "{}\t{}\t{:.1}h\t{:.1}h\t{}",
+                registry.registry,
+                registry.legs,
+                registry.block_hours,
+                registry.estimated_hours,
+                registry.aircraft
+            )?;
+        }
+
+        write!(
+            f,
+            "\n{} unserved airport(s): {}",
+            self.unserved_airports.len(),
+            self.unserved_airports.iter().join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use crate::{cmds::test_setup, report::Report};
+
+    #[test]
+    fn report_normal() -> Result<()> {
+        let (air_facilities, mut file) = test_setup()?;
+        file.flights[0].days = "135".parse()?;
+        let report = Report::new(&file, air_facilities);
+        assert_eq!(report.airports.len(), 2);
+        assert_eq!(
+            (report.airports[0].departures, report.airports[0].arrivals),
+            (3, 0)
+        );
+        assert_eq!(report.busiest_hours[0].hour, 0);
+        assert_eq!(report.routes[0].flights, 3);
+        assert_eq!(report.registries[0].legs, 3);
+        assert!(
+            (report.registries[0].block_hours - 30.0).abs() < f32::EPSILON,
+            "Faulty block hours"
+        );
+        assert!(!report.unserved_airports.is_empty());
+        assert!(report.to_string().contains("ABC\tDEF\t3"), "Faulty table");
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use std::{fs, path::Path};
+
+use chrono::{Local, NaiveDateTime};
+use color_eyre::eyre::{eyre, Result};
+use common::data_types::timetable::AirlineTimetable;
+use itertools::Itertools;
+
+/// Directory next to the timetable that backups are kept in
+const BACKUP_DIR: &str = ".fpln-backups";
+/// Number of backups kept for each timetable, the oldest are deleted first
+const BACKUP_LIMIT: usize = 20;
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S%.6f";
+
+/// Whether the file at `path` is exactly what saving the timetable would write
+pub fn is_saved(file: &AirlineTimetable, path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|saved| saved == file.to_string())
+}
+
+/// Saves the timetable to `path` if it changed, backing up the file it replaces
+pub fn save(file: &AirlineTimetable, path: &Path) -> Result<()> {
+    if is_saved(file, path) {
+        return Ok(());
+    }
+    if path.exists() {
+        backup(path)?;
+    }
+    file.to_file(path)
+}
+
+/// Copies the file into the backup directory as `<name>.<timestamp>.fpln`, then deletes all but
+/// the newest [`BACKUP_LIMIT`] backups of it
+fn backup(path: &Path) -> Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| eyre!("`{}` has no parent directory", path.display()))?
+        .join(BACKUP_DIR);
+    let name = path
+        .file_stem()
+        .ok_or_else(|| eyre!("`{}` has no file name", path.display()))?
+        .to_string_lossy();
+    fs::create_dir_all(&dir)?;
+    fs::copy(
+        path,
+        dir.join(format!(
+            "{name}.{}.fpln",
+            Local::now().format(TIMESTAMP_FORMAT)
+        )),
+    )?;
+
+    // the timestamp is checked so backups of `A.B.fpln` aren't counted as backups of `A.fpln`
+    let backups = fs::read_dir(&dir)?
+        .map_ok(|entry| entry.path())
+        .filter_ok(|backup| {
+            backup
+                .file_name()
+                .and_then(|file_name| file_name.to_str()?.strip_suffix(".fpln"))
+                .and_then(|file_name| file_name.strip_prefix(&*name)?.strip_prefix('.'))
+                .is_some_and(|time| NaiveDateTime::parse_from_str(time, TIMESTAMP_FORMAT).is_ok())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for old in backups.iter().sorted().rev().skip(BACKUP_LIMIT) {
+        fs::remove_file(old)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use color_eyre::eyre::Result;
+
+    use crate::{
+        cmds::{test_setup, TestDir},
+        save::{is_saved, save, BACKUP_DIR, BACKUP_LIMIT},
+    };
+
+    #[test]
+    fn save_backups() -> Result<()> {
+        let dir = TestDir::new()?;
+        let path = dir.join("Test.fpln");
+        let other = dir.join("Test.Other.fpln");
+        let (_, mut file) = test_setup()?;
+
+        save(&file, &other)?;
+        save(&file, &path)?;
+        save(&file, &path)?;
+        assert!(is_saved(&file, &path), "Faulty save");
+        assert!(!dir.join(BACKUP_DIR).exists(), "Unchanged file backed up");
+
+        // saved even though reading the file back would uppercase the airport
+        file.flights[0].segments[0].airport = "abc".into();
+        save(&file, &path)?;
+        assert!(is_saved(&file, &path), "Faulty save");
+        fs::remove_dir_all(dir.join(BACKUP_DIR))?;
+
+        for i in 0..=BACKUP_LIMIT {
+            file.flights[0].registry = format!("REG{i}").into();
+            save(&file, &path)?;
+        }
+        save(&file, &other)?;
+        assert!(is_saved(&file, &path), "Faulty save");
+        let backups = fs::read_dir(dir.join(BACKUP_DIR))?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(backups.len(), BACKUP_LIMIT + 1, "Faulty backups");
+        assert_eq!(
+            backups
+                .iter()
+                .filter(|b| b.starts_with("Test.Other."))
+                .count(),
+            1,
+            "Faulty backups"
+        );
+        Ok(())
+    }
+}
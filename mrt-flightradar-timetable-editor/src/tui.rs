@@ -0,0 +1,660 @@
+use std::io::stdout;
+
+use color_eyre::eyre::Result;
+use common::data_types::{
+    airport::AirFacility,
+    timetable::{AirlineTimetable, Flight},
+};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+    Frame, Terminal,
+};
+
+use crate::cmds::{
+    duration,
+    h::{CMDS, NOTES},
+    run as run_cmd, schedule, Action, State,
+};
+
+const FLIGHT_FIELDS: [&str; 3] = ["a", "reg", "days"];
+const SEGMENT_FIELDS: [&str; 4] = ["f", "a", "d", "r"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Focus {
+    Flights,
+    Segments,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    /// Typing a command, as in the REPL
+    Palette(String),
+    /// Typing a new value for the selected field
+    Edit(String),
+    Help,
+    /// Waiting for `y` to apply a previewed change
+    Confirm(String, Vec<Flight>),
+}
+
+struct App<'a> {
+    file: &'a mut AirlineTimetable,
+    air_facilities: &'a [AirFacility],
+    state: State,
+    /// Selected row of the filtered flights, not the index in the timetable
+    flights: TableState,
+    segments: TableState,
+    focus: Focus,
+    /// Index into [`FLIGHT_FIELDS`] or [`SEGMENT_FIELDS`] depending on the focus
+    field: usize,
+    mode: Mode,
+    /// Output of the last command, and whether it is an error
+    status: Option<(String, bool)>,
+    quit: bool,
+}
+
+impl<'a> App<'a> {
+    fn new(file: &'a mut AirlineTimetable, air_facilities: &'a [AirFacility]) -> Self {
+        let mut app = Self {
+            file,
+            air_facilities,
+            state: State::default(),
+            flights: TableState::default(),
+            segments: TableState::default(),
+            focus: Focus::Flights,
+            field: 0,
+            mode: Mode::Normal,
+            status: None,
+            quit: false,
+        };
+        app.clamp();
+        app
+    }
+
+    /// Indices of the flights shown
+    fn visible(&self) -> Vec<usize> {
+        self.state
+            .filter
+            .apply(&self.file.flights)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Index of the selected flight in the timetable
+    fn flight(&self) -> Option<usize> {
+        self.visible().get(self.flights.selected()?).copied()
+    }
+
+    fn segment(&self) -> Option<usize> {
+        self.segments.selected()
+    }
+
+    /// Name of the selected field, as accepted by `c`
+    fn field_name(&self) -> Option<String> {
+        match self.focus {
+            Focus::Flights => Some(FLIGHT_FIELDS[self.field].into()),
+            Focus::Segments => self
+                .segment()
+                .map(|seg| format!("{}{}", SEGMENT_FIELDS[self.field], seg + 1)),
+        }
+    }
+
+    fn field_value(&self) -> Option<String> {
+        let flight = &self.file.flights[self.flight()?];
+        Some(match self.focus {
+            Focus::Flights => match self.field {
+                0 => flight.aircraft.to_string(),
+                1 => flight.registry.to_string(),
+                _ => flight.days.to_string(),
+            },
+            Focus::Segments => {
+                let seg = &flight.segments[self.segment()?];
+                match self.field {
+                    0 => seg.flight_no.to_string(),
+                    1 => seg.airport.to_string(),
+                    2 => seg.depart_time.to_string(),
+                    _ => seg
+                        .arrive_time
+                        .map_or_else(|| "-".into(), |t| t.to_string()),
+                }
+            }
+        })
+    }
+
+    /// Keeps the selections within bounds after the timetable changes
+    fn clamp(&mut self) {
+        let flights = self.visible().len();
+        self.flights.select(match self.flights.selected() {
+            _ if flights == 0 => None,
+            Some(i) => Some(i.min(flights - 1)),
+            None => Some(0),
+        });
+        let segments = self
+            .flight()
+            .map_or(0, |i| self.file.flights[i].segments.len());
+        self.segments.select(match self.segment() {
+            _ if segments == 0 => None,
+            Some(i) => Some(i.min(segments - 1)),
+            None => Some(0),
+        });
+        if segments == 0 {
+            self.focus = Focus::Flights;
+        }
+        let fields = match self.focus {
+            Focus::Flights => FLIGHT_FIELDS.len(),
+            Focus::Segments => SEGMENT_FIELDS.len(),
+        };
+        self.field = self.field.min(fields - 1);
+    }
+
+    fn move_selection(&mut self, by: isize) {
+        let state = match self.focus {
+            Focus::Flights => &mut self.flights,
+            Focus::Segments => &mut self.segments,
+        };
+        if let Some(i) = state.selected() {
+            state.select(Some(i.saturating_add_signed(by)));
+        }
+        if self.focus == Focus::Flights {
+            *self.segments.offset_mut() = 0;
+        }
+        self.clamp();
+    }
+
+    fn execute(&mut self, cmd_str: &str) {
+        self.status = match run_cmd(cmd_str, self.file, &mut self.state, self.air_facilities) {
+            Ok(Action::Refresh | Action::Hold) => None,
+            Ok(Action::Msg(msg)) => Some((msg, false)),
+            Ok(Action::Confirm(preview, flights)) => {
+                self.mode = Mode::Confirm(preview, flights);
+                None
+            }
+            Ok(Action::Quit(msg)) => {
+                self.quit = true;
+                Some((msg, false))
+            }
+            Err(err) => Some((err.to_string(), true)),
+        };
+        self.clamp();
+    }
+
+    /// Returns whether a command was run, in which case the timetable may have changed
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match &mut self.mode {
+            Mode::Help => {
+                self.mode = Mode::Normal;
+                false
+            }
+            Mode::Confirm(..) => {
+                let Mode::Confirm(_, flights) = std::mem::replace(&mut self.mode, Mode::Normal)
+                else {
+                    unreachable!()
+                };
+                if key.code == KeyCode::Char('y') {
+                    self.state.apply(self.file, flights);
+                    self.status = Some(("Applied".into(), false));
+                    self.clamp();
+                    true
+                } else {
+                    self.status = Some(("Cancelled".into(), false));
+                    false
+                }
+            }
+            Mode::Palette(input) | Mode::Edit(input) => match key.code {
+                KeyCode::Esc => {
+                    self.mode = Mode::Normal;
+                    false
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                    false
+                }
+                KeyCode::Char(c) => {
+                    input.push(c);
+                    false
+                }
+                KeyCode::Enter => {
+                    let mode = std::mem::replace(&mut self.mode, Mode::Normal);
+                    let cmd_str = match mode {
+                        Mode::Palette(input) if input.trim() == "h" => {
+                            self.mode = Mode::Help;
+                            return false;
+                        }
+                        Mode::Palette(input) => input,
+                        Mode::Edit(input) => {
+                            let (Some(flight), Some(field)) = (self.flight(), self.field_name())
+                            else {
+                                return false;
+                            };
+                            format!("c {flight} {field} {input}")
+                        }
+                        Mode::Normal | Mode::Help | Mode::Confirm(..) => unreachable!(),
+                    };
+                    self.execute(&cmd_str);
+                    true
+                }
+                _ => false,
+            },
+            Mode::Normal => {
+                match key.code {
+                    KeyCode::Char('q') => {
+                        self.execute("q");
+                        return true;
+                    }
+                    KeyCode::Char(':') => self.mode = Mode::Palette(String::new()),
+                    KeyCode::Char('?') => self.mode = Mode::Help,
+                    KeyCode::Char('u') => {
+                        self.execute("u");
+                        return true;
+                    }
+                    KeyCode::Enter | KeyCode::Char('e') => {
+                        if let Some(value) = self.field_value() {
+                            self.mode = Mode::Edit(value);
+                        }
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+                    KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+                    KeyCode::PageUp => self.move_selection(-10),
+                    KeyCode::PageDown => self.move_selection(10),
+                    KeyCode::Left => self.field = self.field.saturating_sub(1),
+                    KeyCode::Right => {
+                        self.field += 1;
+                        self.clamp();
+                    }
+                    KeyCode::Tab => {
+                        self.focus = match self.focus {
+                            Focus::Flights if self.segment().is_some() => Focus::Segments,
+                            _ => Focus::Flights,
+                        };
+                        self.clamp();
+                    }
+                    _ => {}
+                }
+                false
+            }
+        }
+    }
+
+    fn header(fields: &[&'static str], focused: bool, field: usize) -> Row<'static> {
+        Row::new(fields.iter().enumerate().map(|(i, name)| {
+            let style = Style::default().fg(Color::Yellow);
+            Cell::from(*name).style(if focused && i == field {
+                style.add_modifier(Modifier::REVERSED)
+            } else {
+                style
+            })
+        }))
+    }
+
+    fn block(&self, title: String, focus: Focus) -> Block<'static> {
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(if self.focus == focus {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            })
+    }
+
+    fn draw_flights(&mut self, f: &mut Frame, area: Rect) {
+        let rows = self
+            .state
+            .filter
+            .apply(&self.file.flights)
+            .map(|(i, flight)| {
+                Row::new([
+                    i.to_string(),
+                    flight.aircraft.to_string(),
+                    flight.registry.to_string(),
+                    schedule(flight),
+                    flight
+                        .segments
+                        .iter()
+                        .map(|seg| seg.airport.as_str())
+                        .collect::<Vec<_>>()
+                        .join("-"),
+                ])
+            });
+        let table = Table::new(rows)
+            .header(Self::header(
+                &[
+                    "#",
+                    "(a) Aircraft",
+                    "(reg) Registry",
+                    "(days) Days",
+                    "Route",
+                ],
+                self.focus == Focus::Flights,
+                self.field + 1,
+            ))
+            .block(self.block(
+                if self.state.filter.is_empty() {
+                    format!("Editing {}", self.file.name)
+                } else {
+                    format!("Editing {} (filter: {})", self.file.name, self.state.filter)
+                },
+                Focus::Flights,
+            ))
+            .widths(&[
+                Constraint::Length(4),
+                Constraint::Percentage(30),
+                Constraint::Length(14),
+                Constraint::Length(11),
+                Constraint::Percentage(45),
+            ])
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ");
+        f.render_stateful_widget(table, area, &mut self.flights);
+    }
+
+    fn draw_segments(&mut self, f: &mut Frame, area: Rect) {
+        let flight = self.flight().map(|i| &self.file.flights[i]);
+        let segments = flight.map(|f| f.segments.as_slice()).unwrap_or_default();
+        let rows = segments.iter().enumerate().map(|(i, seg)| {
+            Row::new([
+                (i + 1).to_string(),
+                seg.flight_no.to_string(),
+                seg.airport.to_string(),
+                seg.depart_time.to_string(),
+                seg.arrive_time.map(|t| t.to_string()).unwrap_or_default(),
+                flight
+                    .and_then(|f| f.block_time(i))
+                    .map(duration)
+                    .unwrap_or_default(),
+            ])
+        });
+        let table = Table::new(rows)
+            .header(Self::header(
+                &[
+                    "N",
+                    "(fN) Flight",
+                    "(aN) Airport",
+                    "(dN) Dep.",
+                    "(rN) Arr.",
+                    "Block",
+                ],
+                self.focus == Focus::Segments,
+                self.field + 1,
+            ))
+            .block(self.block("Segments".into(), Focus::Segments))
+            .widths(&[
+                Constraint::Length(3),
+                Constraint::Length(12),
+                Constraint::Length(13),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(6),
+            ])
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ");
+        f.render_stateful_widget(table, area, &mut self.segments);
+    }
+
+    const fn popup(area: Rect) -> Rect {
+        Rect {
+            x: area.width / 8,
+            y: area.height / 8,
+            width: area.width * 3 / 4,
+            height: area.height * 3 / 4,
+        }
+    }
+
+    fn draw_confirm(f: &mut Frame, area: Rect, preview: &str) {
+        let area = Self::popup(area);
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(format!("{preview}\n\nApply? [y/N]"))
+                .block(Block::default().title("Confirm").borders(Borders::ALL)),
+            area,
+        );
+    }
+
+    fn draw_help(f: &mut Frame, area: Rect) {
+        let area = Self::popup(area);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                "Keys",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from("Up/Down/j/k: select   Tab: switch pane   Left/Right: select field"),
+            Line::from("Enter/e: edit field   :  command palette   u: undo   ?: help   q: quit"),
+            Line::from(""),
+        ];
+        lines.extend(NOTES.iter().map(|note| {
+            Line::from(vec![
+                Span::styled("Note: ", Style::default().fg(Color::Yellow)),
+                Span::raw(*note),
+            ])
+        }));
+        lines.push(Line::from(""));
+        lines.extend(CMDS.iter().map(|(cmd, args, desc)| {
+            Line::from(vec![
+                Span::styled(
+                    *cmd,
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" "),
+                Span::styled(*args, Style::default().fg(Color::Yellow)),
+                Span::raw(format!(": {desc}")),
+            ])
+        }));
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::default().title("Help").borders(Borders::ALL)),
+            area,
+        );
+    }
+
+    fn draw(&mut self, f: &mut Frame) {
+        let [main, status, input] = *Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(f.size())
+        else {
+            return;
+        };
+        let [flights, segments] = *Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(main)
+        else {
+            return;
+        };
+        self.draw_flights(f, flights);
+        self.draw_segments(f, segments);
+
+        let warnings = self.state.fleet.check(&self.file.flights);
+        f.render_widget(
+            match &self.status {
+                Some((msg, true)) => Paragraph::new(msg.as_str())
+                    .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                Some((msg, false)) => {
+                    Paragraph::new(msg.as_str()).style(Style::default().fg(Color::Yellow))
+                }
+                None => match warnings.first() {
+                    Some(warning) => Paragraph::new(format!(
+                        "Warning: {warning} ({} warning(s))",
+                        warnings.len()
+                    ))
+                    .style(Style::default().fg(Color::Red)),
+                    None => Paragraph::new("Press ? for help, : for commands"),
+                },
+            },
+            status,
+        );
+
+        let prompt = match &self.mode {
+            Mode::Palette(input) => Some(format!(":{input}")),
+            Mode::Edit(input) => self.field_name().map(|field| format!("{field} = {input}")),
+            Mode::Normal | Mode::Help | Mode::Confirm(..) => None,
+        };
+        if let Some(prompt) = prompt {
+            let width = u16::try_from(prompt.chars().count()).unwrap_or(u16::MAX);
+            f.render_widget(Paragraph::new(prompt), input);
+            f.set_cursor(input.x.saturating_add(width), input.y);
+        }
+
+        match &self.mode {
+            Mode::Help => Self::draw_help(f, f.size()),
+            Mode::Confirm(preview, _) => Self::draw_confirm(f, f.size(), preview),
+            Mode::Normal | Mode::Palette(_) | Mode::Edit(_) => {}
+        }
+    }
+}
+
+/// Restores the terminal even if the editor errors or panics
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> Result<Self> {
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(stdout(), LeaveAlternateScreen);
+    }
+}
+
+/// Full-screen editor, with the REPL commands available from the command palette.
+/// The timetable and the rest of the workspace are saved after every command, unless autosave is off
+pub fn run(
+    file: &mut AirlineTimetable,
+    state: State,
+    air_facilities: &[AirFacility],
+) -> Result<()> {
+    let _guard = TerminalGuard::new()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    let mut app = App::new(file, air_facilities);
+    app.state = state;
+
+    while !app.quit {
+        terminal.draw(|f| app.draw(f))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && app.handle_key(key) {
+                app.state.autosave(app.file)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::{
+        cmds::test_setup,
+        tui::{App, Focus, Mode},
+    };
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                '\t' => KeyCode::Tab,
+                '\x08' => KeyCode::Backspace,
+                c => KeyCode::Char(c),
+            };
+            app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn tui_inline_edit() -> Result<()> {
+        let (air_facilities, mut file) = test_setup()?;
+        let mut app = App::new(&mut file, air_facilities);
+        press(&mut app, "\t");
+        app.handle_key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
+        assert_eq!(app.focus, Focus::Segments);
+        assert_eq!(app.field_name().as_deref(), Some("a1"));
+
+        press(&mut app, "e");
+        assert_eq!(app.mode, Mode::Edit("ABC".into()));
+        press(&mut app, "\x08\x08\x08XYZ\n");
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.status, None);
+        assert_eq!(file.flights[0].segments[0].airport, "XYZ");
+        Ok(())
+    }
+
+    #[test]
+    fn tui_palette() -> Result<()> {
+        let (air_facilities, mut file) = test_setup()?;
+        let mut app = App::new(&mut file, air_facilities);
+        press(&mut app, ":d 0\n");
+        assert_eq!(app.flight(), None);
+        assert!(file.flights.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn tui_filter() -> Result<()> {
+        let (air_facilities, mut file) = test_setup()?;
+        file.flights.insert(0, file.flights[0].to_owned());
+        file.flights[0].registry = "OTHER".into();
+        let mut app = App::new(&mut file, air_facilities);
+        press(&mut app, ":f reg REG\n");
+        assert_eq!(app.flight(), Some(1));
+
+        press(&mut app, "e\x08\x08\x08\x08NEW\n");
+        assert_eq!(app.flight(), Some(1));
+        assert_eq!(file.flights[1].aircraft, "NEW");
+        assert_eq!(file.flights[0].aircraft, "Test");
+        Ok(())
+    }
+
+    #[test]
+    fn tui_confirm() -> Result<()> {
+        let (air_facilities, mut file) = test_setup()?;
+        let mut app = App::new(&mut file, air_facilities);
+        press(&mut app, ":bap ABC XYZ\n");
+        assert!(matches!(app.mode, Mode::Confirm(..)));
+        press(&mut app, "n");
+        assert_eq!(app.mode, Mode::Normal);
+
+        press(&mut app, ":bap ABC XYZ\ny");
+        assert_eq!(file.flights[0].segments[0].airport, "XYZ");
+        Ok(())
+    }
+
+    #[test]
+    fn tui_palette_error() -> Result<()> {
+        let (air_facilities, mut file) = test_setup()?;
+        let mut app = App::new(&mut file, air_facilities);
+        press(&mut app, ":x\n");
+        assert!(matches!(app.status, Some((_, true))));
+
+        press(&mut app, ":h\n");
+        assert_eq!(app.mode, Mode::Help);
+        press(&mut app, "q");
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(!app.quit);
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::Result;
+use common::data_types::{time::Time, timetable::AirlineTimetable};
+use itertools::Itertools;
+use smol_str::SmolStr;
+
+/// Loads every `.fpln` file in a directory along with its path, sorted by airline name
+pub fn load_dir(dir: &Path) -> Result<Vec<(AirlineTimetable, PathBuf)>> {
+    let mut airlines = fs::read_dir(dir)?
+        .map_ok(|entry| entry.path())
+        .filter_ok(|path| path.extension().is_some_and(|ext| ext == "fpln"))
+        .map(|path| {
+            let path = path?;
+            Ok((AirlineTimetable::from_file(path.to_owned())?, path))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    airlines.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+    Ok(airlines)
+}
+
+/// Lists values that more than one airline uses, eg. `REG` is used by `Airline A, Airline B`
+fn shared<'a, K: Ord>(
+    airlines: &[&'a AirlineTimetable],
+    keys: impl Fn(&'a AirlineTimetable) -> Vec<K>,
+) -> BTreeMap<K, BTreeSet<&'a SmolStr>> {
+    let mut users = BTreeMap::<K, BTreeSet<&SmolStr>>::new();
+    for airline in airlines {
+        for key in keys(airline) {
+            users.entry(key).or_default().insert(&airline.name);
+        }
+    }
+    users.retain(|_, airlines| airlines.len() > 1);
+    users
+}
+
+/// Finds conflicts between airlines: registries and flight numbers used by more than one airline,
+/// and departures from the same airport at the same time
+pub fn check(airlines: &[&AirlineTimetable]) -> Vec<String> {
+    let registries = shared(airlines, |airline| {
+        airline
+            .flights
+            .iter()
+            .map(|f| &f.registry)
+            .filter(|reg| !reg.is_empty())
+            .collect()
+    });
+    let flight_nos = shared(airlines, |airline| {
+        airline
+            .flights
+            .iter()
+            .flat_map(|f| &f.segments)
+            .map(|seg| &seg.flight_no)
+            .collect()
+    });
+    let slots = shared(airlines, |airline| {
+        airline
+            .flights
+            .iter()
+            .flat_map(|f| &f.segments)
+            .map(|seg| (&seg.airport, seg.depart_time.time_of_day()))
+            .collect::<Vec<(&SmolStr, Time)>>()
+    });
+
+    registries
+        .into_iter()
+        .map(|(reg, users)| format!("Registry {reg} is used by {}", users.iter().join(", ")))
+        .chain(flight_nos.into_iter().map(|(flight_no, users)| {
+            format!(
+                "Flight number {flight_no} is used by {}",
+                users.iter().join(", ")
+            )
+        }))
+        .chain(slots.into_iter().map(|((airport, time), users)| {
+            format!(
+                "{airport} has departures at {time} by {}",
+                users.iter().join(", ")
+            )
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use color_eyre::eyre::Result;
+    use common::data_types::timetable::AirlineTimetable;
+
+    use crate::{
+        cmds::TestDir,
+        workspace::{check, load_dir},
+    };
+
+    #[test]
+    fn workspace_load_dir() -> Result<()> {
+        let dir = TestDir::new()?;
+        for name in ["B Air", "A Air"] {
+            fs::write(
+                dir.join(format!("{name}.fpln")),
+                include_str!("../../data/test-timetable.fpln"),
+            )?;
+        }
+        fs::write(dir.join("notes.txt"), "")?;
+        let names = load_dir(&dir)?
+            .into_iter()
+            .map(|(a, _)| a.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["A Air", "B Air"], "Faulty workspace loading");
+        Ok(())
+    }
+
+    #[test]
+    fn workspace_check() -> Result<()> {
+        let a = AirlineTimetable::from_string(
+            include_str!("../../data/test-timetable.fpln"),
+            "A Air".into(),
+        )?;
+        let mut b = a.to_owned();
+        b.name = "B Air".into();
+        assert_eq!(check(&[&a]), Vec::<String>::new(), "Faulty single check");
+
+        let issues = check(&[&a, &b]);
+        assert_eq!(issues.len(), 5, "Faulty check: {issues:?}");
+        assert_eq!(issues[0], "Registry REG is used by A Air, B Air");
+
+        b.flights[0].registry = "OTHER".into();
+        b.flights[0].segments.clear();
+        assert!(check(&[&a, &b]).is_empty(), "Faulty check");
+        Ok(())
+    }
+}